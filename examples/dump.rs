@@ -1,22 +1,40 @@
-extern crate device_tree;
+extern crate flat_device_tree;
 
+use std::env;
 use std::fs;
 use std::io::Read;
 use std::io::Write;
 
 fn main() {
+    let validate = env::args().skip(1).any(|arg| arg == "--validate");
+
     // read file into memory
     let mut input = fs::File::open("sample.dtb").unwrap();
     let mut buf = Vec::new();
     input.read_to_end(&mut buf).unwrap();
 
-    let dt = device_tree::DeviceTree::load(buf.as_slice()).unwrap();
+    let dt = flat_device_tree::DeviceTree::from_bytes(&buf).unwrap();
     println!("{:?}", dt);
 
+    for entry in dt.memory_reservations() {
+        println!("/memreserve/ {:#x} {:#x};", entry.address, entry.size);
+    }
+
+    for (alias, path) in dt.aliases() {
+        println!("{} = {}", alias, path);
+    }
+
+    if validate {
+        for warning in dt.validate() {
+            println!("warning: {:?}", warning);
+        }
+    }
+
     let dtb = dt.store().unwrap();
     let mut output = fs::OpenOptions::new()
         .write(true)
         .create(true)
+        .truncate(true)
         .open("output.dtb")
         .unwrap();
     output.write_all(&dtb).unwrap();
@@ -0,0 +1,353 @@
+use alloc::{vec, vec::Vec};
+
+use crate::property::read_cells;
+use crate::{DeviceTree, Node};
+
+/// Default `#address-cells` of a node that does not specify it.
+const DEFAULT_ADDRESS_CELLS: u32 = 2;
+
+/// Default `#size-cells` of a node that does not specify it.
+const DEFAULT_SIZE_CELLS: u32 = 1;
+
+/// A single `(address, size)` entry of a `reg` property.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RegEntry {
+    /// Address of the region, in the address space of the parent node.
+    pub address: u64,
+
+    /// Size of the region in bytes. Always `0` if the parent has a
+    /// `#size-cells` of `0`.
+    pub size: u64,
+}
+
+/// A single entry of a `ranges` property, mapping a window of a bus address
+/// space into the address space of its parent.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AddressRange {
+    /// Start of the window in the address space of the bus.
+    pub child_address: u64,
+
+    /// Start of the window in the address space of the bus's parent.
+    pub parent_address: u64,
+
+    /// Size of the window in bytes.
+    pub size: u64,
+}
+
+impl AddressRange {
+    /// Map `addr` from the bus into the parent address space, if it falls
+    /// within this range.
+    pub fn translate(&self, addr: u64) -> Option<u64> {
+        let offset = addr.checked_sub(self.child_address)?;
+        if offset >= self.size {
+            return None;
+        }
+
+        self.parent_address.checked_add(offset)
+    }
+}
+
+impl Node {
+    /// The `#address-cells` that applies to the children of this node,
+    /// falling back to the default from the specification.
+    ///
+    /// Returns `None` if the property is not a single cell.
+    pub(crate) fn address_cells(&self) -> Option<u32> {
+        self.cell_count("#address-cells", DEFAULT_ADDRESS_CELLS)
+    }
+
+    /// The `#size-cells` that applies to the children of this node, falling
+    /// back to the default from the specification.
+    ///
+    /// Returns `None` if the property is not a single cell.
+    pub(crate) fn size_cells(&self) -> Option<u32> {
+        self.cell_count("#size-cells", DEFAULT_SIZE_CELLS)
+    }
+
+    fn cell_count(&self, name: &str, default: u32) -> Option<u32> {
+        match self.property(name) {
+            Some(prop) => prop.as_u32(),
+            None => Some(default),
+        }
+    }
+
+    /// The entries of the `reg` property, decoded using the
+    /// `#address-cells` and `#size-cells` of `parent`.
+    ///
+    /// Returns `None` if there is no `reg` property, if either cell count
+    /// is malformed or larger than 2, or if the value's length is not a
+    /// multiple of the entry size.
+    pub fn reg<'a>(
+        &'a self,
+        parent: &Node,
+    ) -> Option<impl Iterator<Item = RegEntry> + 'a> {
+        self.decode_reg("reg", parent)
+    }
+
+    /// The raw value of the `reg` property.
+    pub fn reg_raw(&self) -> Option<&[u8]> {
+        Some(self.property("reg")?.data())
+    }
+
+    /// The `reg` property as `(address, size)` pairs, given the
+    /// `#address-cells` and `#size-cells` of the parent node.
+    ///
+    /// See [`Property::as_reg`](crate::Property::as_reg).
+    pub fn reg_cells(
+        &self,
+        addr_cells: u32,
+        size_cells: u32,
+    ) -> Option<Vec<(u64, u64)>> {
+        self.property("reg")?.as_reg(addr_cells, size_cells)
+    }
+
+    /// Decode a property in the format of `reg`.
+    pub(crate) fn decode_reg<'a>(
+        &'a self,
+        name: &str,
+        parent: &Node,
+    ) -> Option<impl Iterator<Item = RegEntry> + 'a> {
+        let address_cells = parent.address_cells()?;
+        let size_cells = parent.size_cells()?;
+        let data = self.property(name)?.data();
+
+        if address_cells > 2 || size_cells > 2 {
+            return None;
+        }
+
+        let address_len = address_cells as usize * 4;
+        let entry_len = address_len + size_cells as usize * 4;
+        if entry_len == 0 || !data.len().is_multiple_of(entry_len) {
+            return None;
+        }
+
+        Some(data.chunks_exact(entry_len).map(move |entry| {
+            let (address, size) = entry.split_at(address_len);
+            // both widths were checked above, so reading cannot fail
+            RegEntry {
+                address: read_cells(address).unwrap_or(0),
+                size: read_cells(size).unwrap_or(0),
+            }
+        }))
+    }
+
+    /// The entries of the `ranges` property, decoded using the cell counts
+    /// of this node and of `parent`.
+    ///
+    /// Returns `None` if there is no `ranges` property, if any cell count
+    /// is malformed or larger than 2, or if the value's length is not a
+    /// multiple of the entry size. An empty `ranges` property, meaning that
+    /// the bus maps addresses one to one, yields no entries.
+    pub fn ranges<'a>(
+        &'a self,
+        parent: &Node,
+    ) -> Option<impl Iterator<Item = AddressRange> + 'a> {
+        self.decode_ranges("ranges", parent)
+    }
+
+    /// The entries of the `dma-ranges` property, mapping addresses that
+    /// devices on this bus use for DMA into the address space of `parent`.
+    ///
+    /// Decoded like [`ranges`](Node::ranges).
+    pub fn dma_ranges<'a>(
+        &'a self,
+        parent: &Node,
+    ) -> Option<impl Iterator<Item = AddressRange> + 'a> {
+        self.decode_ranges("dma-ranges", parent)
+    }
+
+    /// The entries of the `ranges` property, decoded using the given
+    /// `#address-cells` of this node and of its parent and `#size-cells` of
+    /// this node.
+    ///
+    /// Like [`ranges`](Node::ranges), but for callers that already know the
+    /// cell counts.
+    pub fn ranges_cells(
+        &self,
+        child_addr_cells: u32,
+        parent_addr_cells: u32,
+        size_cells: u32,
+    ) -> Option<Vec<AddressRange>> {
+        let data = self.property("ranges")?.data();
+        let ranges = decode_ranges(
+            data,
+            child_addr_cells,
+            parent_addr_cells,
+            size_cells,
+        )?;
+
+        Some(ranges.collect())
+    }
+
+    /// Decode a property in the format of `ranges`.
+    fn decode_ranges<'a>(
+        &'a self,
+        name: &str,
+        parent: &Node,
+    ) -> Option<impl Iterator<Item = AddressRange> + 'a> {
+        let child_cells = self.address_cells()?;
+        let size_cells = self.size_cells()?;
+        let parent_cells = parent.address_cells()?;
+        let data = self.property(name)?.data();
+
+        decode_ranges(data, child_cells, parent_cells, size_cells)
+    }
+
+    /// Map `addr` from the address space of this bus into that of `parent`
+    /// using the named ranges property.
+    fn translate_through(
+        &self,
+        name: &str,
+        parent: &Node,
+        addr: u64,
+    ) -> Option<u64> {
+        if self.property(name)?.is_empty() {
+            return Some(addr);
+        }
+
+        self.decode_ranges(name, parent)?
+            .find_map(|range| range.translate(addr))
+    }
+}
+
+/// Decode a value in the format of `ranges` with the given cell counts.
+fn decode_ranges(
+    data: &[u8],
+    child_cells: u32,
+    parent_cells: u32,
+    size_cells: u32,
+) -> Option<impl Iterator<Item = AddressRange> + '_> {
+    if child_cells > 2 || parent_cells > 2 || size_cells > 2 {
+        return None;
+    }
+
+    let child_len = child_cells as usize * 4;
+    let parent_len = parent_cells as usize * 4;
+    let entry_len = child_len + parent_len + size_cells as usize * 4;
+    if entry_len == 0 || !data.len().is_multiple_of(entry_len) {
+        return None;
+    }
+
+    Some(data.chunks_exact(entry_len).map(move |entry| {
+        let (child, rest) = entry.split_at(child_len);
+        let (parent, size) = rest.split_at(parent_len);
+        // all widths were checked above, so reading cannot fail
+        AddressRange {
+            child_address: read_cells(child).unwrap_or(0),
+            parent_address: read_cells(parent).unwrap_or(0),
+            size: read_cells(size).unwrap_or(0),
+        }
+    }))
+}
+
+impl DeviceTree {
+    /// The nodes along an absolute path, starting with the root node and
+    /// ending with the node the path points to.
+    pub(crate) fn path_nodes<'a>(
+        &'a self,
+        path: &str,
+    ) -> Option<Vec<&'a Node>> {
+        let mut nodes = vec![&self.root];
+        for name in path.strip_prefix('/')?.split('/') {
+            if name.is_empty() {
+                continue;
+            }
+
+            let node = *nodes.last()?;
+            let idx = node.find_segment(name)?;
+            nodes.push(&node.children[idx]);
+        }
+
+        Some(nodes)
+    }
+
+    /// The `#address-cells` that governs the `reg` property of the node at
+    /// `path`.
+    ///
+    /// This is the value of the parent node, not of the node itself, or the
+    /// default of 2 if the parent does not specify it. The root node has no
+    /// parent, so its own value is used. Returns `None` if there is no such
+    /// node or the property is not a single cell.
+    pub fn address_cells(&self, path: &str) -> Option<u32> {
+        self.bus_of(path)?.address_cells()
+    }
+
+    /// The `#size-cells` that governs the `reg` property of the node at
+    /// `path`.
+    ///
+    /// Resolved like [`address_cells`](DeviceTree::address_cells), with a
+    /// default of 1.
+    pub fn size_cells(&self, path: &str) -> Option<u32> {
+        self.bus_of(path)?.size_cells()
+    }
+
+    /// The `reg` property of the node at `path` as `(address, size)`
+    /// pairs, decoded using the cell counts of its parent.
+    ///
+    /// The cell counts are resolved like
+    /// [`address_cells`](DeviceTree::address_cells).
+    pub fn reg_for_node(&self, path: &str) -> Option<Vec<(u64, u64)>> {
+        let bus = self.bus_of(path)?;
+        let node = self.find(path)?;
+
+        node.reg_cells(bus.address_cells()?, bus.size_cells()?)
+    }
+
+    /// The parent of the node at `path`, or the root node itself.
+    fn bus_of(&self, path: &str) -> Option<&Node> {
+        let nodes = self.path_nodes(path)?;
+        Some(nodes[nodes.len().saturating_sub(2)])
+    }
+
+    /// Translate `addr`, an address on the bus the node at `node_path` sits
+    /// on (such as a value from its `reg` property), into a CPU physical
+    /// address.
+    ///
+    /// The address is mapped through the `ranges` property of every bus
+    /// between the node and the root. Returns `None` if a bus has no
+    /// `ranges` property, so its addresses are not visible to the CPU, or
+    /// if the address falls outside all of its ranges.
+    pub fn translate_address(&self, node_path: &str, addr: u64) -> Option<u64> {
+        self.translate_path(node_path, addr, |bus, parent, addr| {
+            bus.translate_through("ranges", parent, addr)
+        })
+    }
+
+    /// Translate `addr`, an address the device at `node_path` uses for DMA,
+    /// into a CPU physical address.
+    ///
+    /// The address is mapped through the `dma-ranges` property of every bus
+    /// between the node and the root. Buses without `dma-ranges` pass
+    /// addresses through unchanged. Returns `None` if the address falls
+    /// outside all ranges of a bus.
+    pub fn translate_dma_address(
+        &self,
+        node_path: &str,
+        addr: u64,
+    ) -> Option<u64> {
+        self.translate_path(node_path, addr, |bus, parent, addr| {
+            if !bus.has_property("dma-ranges") {
+                return Some(addr);
+            }
+            bus.translate_through("dma-ranges", parent, addr)
+        })
+    }
+
+    /// Map `addr` across every ancestor of the node at `path`, except the
+    /// root, using `translate(bus, parent, addr)`.
+    fn translate_path(
+        &self,
+        path: &str,
+        addr: u64,
+        translate: impl Fn(&Node, &Node, u64) -> Option<u64>,
+    ) -> Option<u64> {
+        let nodes = self.path_nodes(path)?;
+        // every ancestor is a bus, with its own parent before it
+        let buses = &nodes[..nodes.len() - 1];
+
+        buses
+            .windows(2)
+            .rev()
+            .try_fold(addr, |addr, pair| translate(pair[1], pair[0], addr))
+    }
+}
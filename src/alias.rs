@@ -0,0 +1,29 @@
+use crate::{DeviceTree, PropertyValue};
+
+impl DeviceTree {
+    /// The path an alias in `/aliases` refers to, as stored in the tree.
+    ///
+    /// Returns `None` if there is no `/aliases` node, no such alias, or its
+    /// value is not a single string. The path is not checked; use
+    /// [`find_node`](DeviceTree::find_node) with the alias to look up the
+    /// node.
+    pub fn resolve_alias(&self, alias: &str) -> Option<&str> {
+        self.aliases()
+            .find(|(name, _)| *name == alias)
+            .map(|(_, path)| path)
+    }
+
+    /// Iterate over the `(alias, path)` pairs of `/aliases`, in document
+    /// order.
+    ///
+    /// Properties whose value is not a single string are skipped.
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.find("/aliases")
+            .into_iter()
+            .flat_map(|aliases| aliases.props.iter())
+            .filter_map(|prop| match prop.value() {
+                PropertyValue::String(path) => Some((prop.name(), path)),
+                _ => None,
+            })
+    }
+}
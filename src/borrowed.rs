@@ -0,0 +1,313 @@
+use alloc::{borrow::ToOwned, vec::Vec};
+use core::str;
+
+use crate::util::{align, SliceRead};
+use crate::{
+    skip_nops, DeviceTree, DeviceTreeHeader, Error, MemoryReservation, Node,
+    Property, Result, OF_DT_BEGIN_NODE, OF_DT_END_NODE, OF_DT_PROP,
+    SUPPORTED_VERSION,
+};
+
+/// A device tree whose names and property values borrow from the blob it
+/// was loaded from.
+///
+/// Loading a tree this way only allocates for the lists of nodes and
+/// properties, not for their contents. Use [`DeviceTree`] to modify or
+/// store a tree.
+#[derive(Clone, Debug)]
+pub struct BorrowedTree<'a> {
+    /// Version, as indicated by version header
+    pub version: u32,
+
+    /// The number of the CPU the system boots from
+    pub boot_cpuid_phys: u32,
+
+    /// Memory regions reserved through the memory reservation block.
+    pub reserved: Vec<MemoryReservation>,
+
+    /// The root node.
+    pub root: BorrowedNode<'a>,
+
+    header: DeviceTreeHeader,
+}
+
+/// A node of a [`BorrowedTree`].
+#[derive(Clone, Debug)]
+pub struct BorrowedNode<'a> {
+    /// The name of the node, as it appears in the node path.
+    pub name: &'a str,
+
+    /// A list of node properties.
+    pub props: Vec<BorrowedProperty<'a>>,
+
+    /// Child nodes of this node.
+    pub children: Vec<BorrowedNode<'a>>,
+
+    struct_offset: usize,
+}
+
+impl PartialEq for BorrowedNode<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.props == other.props
+            && self.children == other.children
+    }
+}
+
+impl Eq for BorrowedNode<'_> {}
+
+/// A property of a [`BorrowedNode`].
+#[derive(Clone, Copy, Debug)]
+pub struct BorrowedProperty<'a> {
+    /// The name of the property.
+    pub name: &'a str,
+
+    /// The raw value of the property.
+    pub data: &'a [u8],
+
+    data_offset: usize,
+}
+
+impl PartialEq for BorrowedProperty<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.data == other.data
+    }
+}
+
+impl Eq for BorrowedProperty<'_> {}
+
+impl<'a> BorrowedTree<'a> {
+    /// Load a device tree from a blob without copying names or property
+    /// values.
+    ///
+    /// The blob is checked the same way as by [`DeviceTree::load`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::BorrowedTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = BorrowedTree::load(buf).unwrap();
+    ///
+    /// let uart = dt.find("/soc/uart@7e201000").unwrap();
+    /// assert_eq!(uart.property("status").unwrap().as_str(), Some("okay"));
+    /// ```
+    pub fn load(buffer: &'a [u8]) -> Result<BorrowedTree<'a>> {
+        let header = DeviceTreeHeader::load(buffer)?;
+
+        // check total size
+        if header.totalsize as usize != buffer.len() {
+            return Err(Error::SizeMismatch);
+        }
+
+        // check version
+        let version = header.version;
+        if version != SUPPORTED_VERSION {
+            return Err(Error::VersionNotSupported);
+        }
+        header.validate()?;
+
+        let off_dt_struct = header.off_dt_struct as usize;
+        let off_dt_strings = header.off_dt_strings as usize;
+        let off_mem_rsvmap = header.off_mem_rsvmap as usize;
+        let boot_cpuid_phys = header.boot_cpuid_phys;
+
+        // load reserved memory list
+        let mut reserved = Vec::new();
+        let mut pos = off_mem_rsvmap;
+
+        loop {
+            let address = buffer.read_be_u64(pos)?;
+            pos += 8;
+            let size = buffer.read_be_u64(pos)?;
+            pos += 8;
+
+            // the list is terminated by an all-zero entry
+            if address == 0 && size == 0 {
+                break;
+            }
+
+            reserved.push(MemoryReservation { address, size });
+        }
+
+        let pos = skip_nops(buffer, off_dt_struct)?;
+        let (_, root) = BorrowedNode::load(buffer, pos, off_dt_strings)?;
+
+        Ok(BorrowedTree {
+            version,
+            boot_cpuid_phys,
+            reserved,
+            root,
+            header,
+        })
+    }
+
+    /// The header of the blob the tree was loaded from.
+    pub fn header(&self) -> &DeviceTreeHeader {
+        &self.header
+    }
+
+    /// The root node of the tree.
+    pub fn root(&self) -> &BorrowedNode<'a> {
+        &self.root
+    }
+
+    /// Find the node at an absolute path, matching each component exactly.
+    pub fn find(&self, path: &str) -> Option<&BorrowedNode<'a>> {
+        self.root.find(path.strip_prefix('/')?)
+    }
+
+    /// Copy the tree into an owned [`DeviceTree`].
+    pub fn to_tree(&self) -> DeviceTree {
+        DeviceTree {
+            version: self.version,
+            boot_cpuid_phys: self.boot_cpuid_phys,
+            reserved: self.reserved.clone(),
+            root: self.root.to_node(),
+            header: self.header,
+        }
+    }
+}
+
+impl<'a> BorrowedNode<'a> {
+    fn load(
+        buffer: &'a [u8],
+        start: usize,
+        off_dt_strings: usize,
+    ) -> Result<(usize, BorrowedNode<'a>)> {
+        // check for DT_BEGIN_NODE
+        if buffer.read_be_u32(start)? != OF_DT_BEGIN_NODE {
+            return Err(Error::ParseError(start));
+        }
+
+        let raw_name = buffer.read_bstring0(start + 4)?;
+
+        // read all the props
+        let mut pos = align(start + 4 + raw_name.len() + 1, 4);
+        pos = skip_nops(buffer, pos)?;
+
+        let mut props = Vec::new();
+
+        while buffer.read_be_u32(pos)? == OF_DT_PROP {
+            let val_size = buffer.read_be_u32(pos + 4)? as usize;
+            let name_offset = buffer.read_be_u32(pos + 8)? as usize;
+
+            // get value slice
+            let val_start = pos + 12;
+            let val_end = val_start + val_size;
+            let val = buffer.subslice(val_start, val_end)?;
+
+            // lookup name in strings table
+            let prop_name =
+                buffer.read_bstring0(off_dt_strings + name_offset)?;
+
+            props.push(BorrowedProperty {
+                name: str::from_utf8(prop_name)?,
+                data: val,
+                data_offset: val_start,
+            });
+
+            pos = skip_nops(buffer, align(val_end, 4))?;
+        }
+
+        // finally, parse children
+        let mut children = Vec::new();
+
+        while buffer.read_be_u32(pos)? == OF_DT_BEGIN_NODE {
+            let (new_pos, child_node) =
+                BorrowedNode::load(buffer, pos, off_dt_strings)?;
+            pos = skip_nops(buffer, new_pos)?;
+
+            children.push(child_node);
+        }
+
+        if buffer.read_be_u32(pos)? != OF_DT_END_NODE {
+            return Err(Error::ParseError(pos));
+        }
+
+        pos += 4;
+
+        Ok((
+            pos,
+            BorrowedNode {
+                name: str::from_utf8(raw_name)?,
+                props,
+                children,
+                struct_offset: start,
+            },
+        ))
+    }
+
+    /// Find a descendant by a path relative to this node, matching each
+    /// component exactly.
+    pub fn find(&self, path: &str) -> Option<&BorrowedNode<'a>> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self, |node, name| node.find_child(name))
+    }
+
+    /// The direct child with exactly the given name.
+    pub fn find_child(&self, name: &str) -> Option<&BorrowedNode<'a>> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    /// Look up the property with the given name.
+    pub fn property(&self, name: &str) -> Option<&BorrowedProperty<'a>> {
+        self.props.iter().find(|prop| prop.name == name)
+    }
+
+    /// Offset of the `FDT_BEGIN_NODE` token of this node in the blob.
+    pub fn struct_offset(&self) -> usize {
+        self.struct_offset
+    }
+
+    /// Copy the node and everything below it into an owned [`Node`].
+    ///
+    /// The copy keeps the offsets of the node and its properties.
+    pub fn to_node(&self) -> Node {
+        Node {
+            name: self.name.to_owned(),
+            props: self.props.iter().map(|prop| prop.to_property()).collect(),
+            children: self.children.iter().map(|n| n.to_node()).collect(),
+            struct_offset: Some(self.struct_offset),
+        }
+    }
+}
+
+impl<'a> BorrowedProperty<'a> {
+    /// The value as a single big-endian `u32`.
+    ///
+    /// Returns `None` unless the value is exactly 4 bytes long.
+    pub fn as_u32(&self) -> Option<u32> {
+        if self.data.len() != 4 {
+            return None;
+        }
+
+        self.data.read_be_u32(0).ok()
+    }
+
+    /// The value as a NUL-terminated string.
+    ///
+    /// Only the string up to the first NUL is returned. Returns `None` if
+    /// the value has no terminator or is not valid UTF-8.
+    pub fn as_str(&self) -> Option<&'a str> {
+        let end = self.data.iter().position(|&b| b == 0)?;
+
+        str::from_utf8(&self.data[..end]).ok()
+    }
+
+    /// Offset of the value in the blob.
+    pub fn data_offset(&self) -> usize {
+        self.data_offset
+    }
+
+    /// Copy the property into an owned [`Property`], keeping its offset.
+    pub fn to_property(&self) -> Property {
+        Property {
+            name: self.name.to_owned(),
+            data: self.data.to_vec(),
+            data_offset: Some(self.data_offset),
+        }
+    }
+}
@@ -0,0 +1,118 @@
+use alloc::{borrow::ToOwned, vec::Vec};
+
+use crate::name::{validate_contents, validate_node};
+use crate::{DeviceTree, MemoryReservation, Node, Property, Result};
+
+/// Builder for constructing a device tree from scratch.
+///
+/// Names are checked when the tree is built, so the node and property
+/// methods can be chained freely.
+///
+/// # Examples
+///
+/// ```rust
+/// use flat_device_tree::DeviceTreeBuilder;
+///
+/// let mut builder = DeviceTreeBuilder::new();
+/// builder.memory_reservation(0x1000_0000, 0x1000);
+///
+/// let mut root = builder.root("");
+/// root.property_u32("#address-cells", 1)
+///     .property_u32("#size-cells", 1)
+///     .property_str("model", "example");
+/// root.child("memory@0")
+///     .property_str("device_type", "memory")
+///     .property("reg", &[0, 0, 0, 0, 0, 0, 0x10, 0]);
+///
+/// let dt = builder.build().unwrap();
+/// assert!(dt.find("/memory@0").is_some());
+/// ```
+#[derive(Debug)]
+pub struct DeviceTreeBuilder {
+    root: Node,
+    reserved: Vec<MemoryReservation>,
+}
+
+impl Default for DeviceTreeBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeviceTreeBuilder {
+    /// Create a builder for an empty tree.
+    pub fn new() -> DeviceTreeBuilder {
+        DeviceTreeBuilder {
+            root: Node::new(""),
+            reserved: Vec::new(),
+        }
+    }
+
+    /// Name the root node and return a builder for it.
+    ///
+    /// The root node of a device tree is normally unnamed, so `name` should
+    /// usually be empty.
+    pub fn root(&mut self, name: &str) -> NodeBuilder<'_> {
+        self.root.name = name.to_owned();
+        NodeBuilder {
+            node: &mut self.root,
+        }
+    }
+
+    /// Add an entry to the memory reservation block.
+    pub fn memory_reservation(&mut self, address: u64, size: u64) -> &mut Self {
+        self.reserved.push(MemoryReservation { address, size });
+        self
+    }
+
+    /// Validate all names and build the tree.
+    pub fn build(self) -> Result<DeviceTree> {
+        if self.root.name.is_empty() {
+            validate_contents(&self.root)?;
+        } else {
+            validate_node(&self.root)?;
+        }
+
+        let mut dt = DeviceTree::new(self.root);
+        dt.reserved = self.reserved;
+        Ok(dt)
+    }
+}
+
+/// Builder for a single node, created by [`DeviceTreeBuilder::root`] or
+/// [`NodeBuilder::child`].
+#[derive(Debug)]
+pub struct NodeBuilder<'a> {
+    node: &'a mut Node,
+}
+
+impl NodeBuilder<'_> {
+    /// Add a property with a raw value.
+    pub fn property(&mut self, name: &str, data: &[u8]) -> &mut Self {
+        self.node.props.push(Property::new(name, data.to_vec()));
+        self
+    }
+
+    /// Add a property holding a single big-endian cell.
+    pub fn property_u32(&mut self, name: &str, val: u32) -> &mut Self {
+        self.property(name, &val.to_be_bytes())
+    }
+
+    /// Add a property holding a NUL-terminated string.
+    pub fn property_str(&mut self, name: &str, val: &str) -> &mut Self {
+        let mut data = Vec::with_capacity(val.len() + 1);
+        data.extend_from_slice(val.as_bytes());
+        data.push(0);
+
+        self.property(name, &data)
+    }
+
+    /// Add a child node and return a builder for it.
+    pub fn child(&mut self, name: &str) -> NodeBuilder<'_> {
+        self.node.children.push(Node::new(name));
+
+        NodeBuilder {
+            node: self.node.children.last_mut().unwrap(),
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{DeviceTree, Node};
+
+/// The `/chosen` node, which holds parameters passed by the bootloader.
+///
+/// Created by [`DeviceTree::chosen`].
+#[derive(Clone, Copy, Debug)]
+pub struct Chosen<'a> {
+    tree: &'a DeviceTree,
+    node: &'a Node,
+}
+
+/// Parity setting of a serial console.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Parity {
+    None,
+    Odd,
+    Even,
+}
+
+/// Serial console settings, parsed from options such as `115200n8`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SerialOptions {
+    /// The baud rate.
+    pub baud: u32,
+
+    /// The parity, `None` unless given.
+    pub parity: Parity,
+
+    /// The number of data bits, 8 unless given.
+    pub data_bits: u8,
+
+    /// Whether RTS/CTS flow control is used, given by a trailing `r`.
+    pub flow_control: bool,
+}
+
+impl SerialOptions {
+    /// Parse options of the form `<baud>[<parity>[<bits>[r]]]`, as used in
+    /// `stdout-path` and the kernel's `console=` parameter.
+    ///
+    /// Returns `None` if `options` does not start with a baud rate.
+    pub fn parse(options: &str) -> Option<SerialOptions> {
+        let digits = options
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(options.len());
+        let baud = options[..digits].parse().ok()?;
+        let mut rest = options[digits..].chars().peekable();
+
+        let parity = match rest.next_if(|c| "noe".contains(*c)) {
+            Some('o') => Parity::Odd,
+            Some('e') => Parity::Even,
+            _ => Parity::None,
+        };
+        let data_bits = rest
+            .next_if(char::is_ascii_digit)
+            .and_then(|c| c.to_digit(10))
+            .map_or(8, |bits| bits as u8);
+        let flow_control = rest.next_if_eq(&'r').is_some();
+
+        Some(SerialOptions {
+            baud,
+            parity,
+            data_bits,
+            flow_control,
+        })
+    }
+}
+
+impl<'a> Chosen<'a> {
+    /// The node itself.
+    pub fn node(&self) -> &'a Node {
+        self.node
+    }
+
+    /// The kernel command line from `bootargs`.
+    pub fn bootargs(&self) -> Option<&'a str> {
+        self.node.property_str("bootargs")
+    }
+
+    /// The path of the console device from `stdout-path`, or the older
+    /// `linux,stdout-path`.
+    ///
+    /// The path may be an alias and may be followed by `:` and options such
+    /// as the baud rate, e.g. `serial0:115200n8`.
+    pub fn stdout_path(&self) -> Option<&'a str> {
+        self.node
+            .property_str("stdout-path")
+            .or_else(|| self.node.property_str("linux,stdout-path"))
+    }
+
+    /// The console device [`stdout_path`](Chosen::stdout_path) refers to,
+    /// resolving aliases and ignoring any options.
+    pub fn stdout_node(&self) -> Option<&'a Node> {
+        self.tree.find_node(self.stdout_path()?)
+    }
+
+    /// The serial settings following the `:` in
+    /// [`stdout_path`](Chosen::stdout_path), if there are any.
+    pub fn stdout_options(&self) -> Option<SerialOptions> {
+        let (_, options) = self.stdout_path()?.split_once(':')?;
+        SerialOptions::parse(options)
+    }
+
+    /// The physical start and end address of the initial ramdisk, from
+    /// `linux,initrd-start` and `linux,initrd-end`. The end is exclusive.
+    ///
+    /// Each property may be 32 or 64 bits wide, independently of the other.
+    /// Returns `None` unless both are present.
+    pub fn initrd(&self) -> Option<(u64, u64)> {
+        Some((self.initrd_start()?, self.initrd_end()?))
+    }
+
+    /// The physical start address of the initial ramdisk from
+    /// `linux,initrd-start`, which may be 32 or 64 bits wide.
+    pub fn initrd_start(&self) -> Option<u64> {
+        self.node.property("linux,initrd-start")?.as_u64()
+    }
+
+    /// The physical end address of the initial ramdisk from
+    /// `linux,initrd-end`, which may be 32 or 64 bits wide.
+    pub fn initrd_end(&self) -> Option<u64> {
+        self.node.property("linux,initrd-end")?.as_u64()
+    }
+}
+
+impl DeviceTree {
+    /// The `/chosen` node, which holds parameters passed by the bootloader.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use flat_device_tree::DeviceTreeBuilder;
+    /// # let mut builder = DeviceTreeBuilder::new();
+    /// # let mut root = builder.root("");
+    /// # root.child("aliases").property_str("serial0", "/uart@1000");
+    /// # root.child("chosen").property_str("stdout-path", "serial0:115200n8");
+    /// # root.child("uart@1000");
+    /// # let dt = builder.build().unwrap();
+    /// let chosen = dt.chosen().unwrap();
+    /// let console = chosen.stdout_node().unwrap();
+    /// let options = chosen.stdout_options().unwrap();
+    ///
+    /// assert_eq!(console.name(), "uart@1000");
+    /// assert_eq!(options.baud, 115200);
+    /// ```
+    pub fn chosen(&self) -> Option<Chosen<'_>> {
+        Some(Chosen {
+            tree: self,
+            node: self.find("/chosen")?,
+        })
+    }
+
+    /// The kernel command line from `/chosen/bootargs`.
+    pub fn bootargs(&self) -> Option<&str> {
+        self.chosen()?.bootargs()
+    }
+
+    /// The path of the console device from `/chosen`.
+    ///
+    /// See [`Chosen::stdout_path`].
+    pub fn stdout_path(&self) -> Option<&str> {
+        self.chosen()?.stdout_path()
+    }
+
+    /// The physical start address of the initial ramdisk from
+    /// `/chosen/linux,initrd-start`, which may be 32 or 64 bits wide.
+    pub fn initrd_start(&self) -> Option<u64> {
+        self.chosen()?.initrd_start()
+    }
+
+    /// The physical end address of the initial ramdisk from
+    /// `/chosen/linux,initrd-end`, which may be 32 or 64 bits wide.
+    pub fn initrd_end(&self) -> Option<u64> {
+        self.chosen()?.initrd_end()
+    }
+}
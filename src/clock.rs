@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+
+use crate::{DeviceTree, Node, Result};
+
+/// A clock used by a device, from its `clocks` and `clock-names`
+/// properties.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clock<'a> {
+    /// The node providing the clock.
+    pub provider: &'a Node,
+
+    /// The argument cells selecting one of the outputs of the provider.
+    pub args: Vec<u32>,
+
+    /// The name of the clock from `clock-names`, if there is one.
+    pub name: Option<&'a str>,
+}
+
+impl Clock<'_> {
+    /// The rate of the clock in Hz, if the provider is a `fixed-clock`.
+    ///
+    /// The rate of other clocks is configured at run time, so it cannot be
+    /// read from the tree.
+    pub fn frequency(&self) -> Option<u64> {
+        if !self.provider.is_compatible("fixed-clock") {
+            return None;
+        }
+        self.provider.clock_frequency()
+    }
+}
+
+impl Node {
+    /// The clocks of the device, in the order of its `clocks` property.
+    ///
+    /// Each clock is paired with the entry of `clock-names` at the same
+    /// position. If `clock-names` is missing or shorter than `clocks`, the
+    /// remaining clocks have no name. Returns an empty list if there is no
+    /// `clocks` property.
+    pub fn clocks<'a>(
+        &'a self,
+        tree: &'a DeviceTree,
+    ) -> Result<Vec<Clock<'a>>> {
+        if !self.has_property("clocks") {
+            return Ok(Vec::new());
+        }
+
+        let mut names = self
+            .property("clock-names")
+            .into_iter()
+            .flat_map(|prop| prop.strings());
+
+        Ok(tree
+            .parse_phandle_args(self, "clocks", "#clock-cells")?
+            .into_iter()
+            .map(|entry| Clock {
+                provider: entry.target,
+                args: entry.args,
+                name: names.next(),
+            })
+            .collect())
+    }
+
+    /// The clock with the given name in `clock-names`.
+    pub fn clock_by_name<'a>(
+        &'a self,
+        tree: &'a DeviceTree,
+        name: &str,
+    ) -> Result<Option<Clock<'a>>> {
+        Ok(self
+            .clocks(tree)?
+            .into_iter()
+            .find(|clock| clock.name == Some(name)))
+    }
+
+    /// The `clock-frequency` of the node in Hz, which may be 32 or 64 bits
+    /// wide.
+    pub fn clock_frequency(&self) -> Option<u64> {
+        self.property("clock-frequency")?.as_u64()
+    }
+}
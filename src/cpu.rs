@@ -0,0 +1,110 @@
+use crate::{DeviceTree, Node, NodeStatus};
+
+/// A CPU described by a child of `/cpus`.
+///
+/// Created by [`DeviceTree::cpus`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Cpu<'a> {
+    /// The node describing the CPU.
+    pub node: &'a Node,
+
+    /// The hardware ID of the CPU, such as the MPIDR on ARM or the hart ID
+    /// on RISC-V, from the first entry of `reg`.
+    pub id: u64,
+
+    /// The first entry of `compatible`.
+    pub compatible: Option<&'a str>,
+
+    /// The status of the CPU.
+    pub status: NodeStatus,
+
+    /// How the CPU is brought up, from `enable-method`, such as `psci` or
+    /// `spin-table`.
+    pub enable_method: Option<&'a str>,
+
+    /// The clock frequency in Hz, from `clock-frequency`.
+    pub clock_frequency: Option<u64>,
+
+    /// The frequency of the timebase in Hz, from `timebase-frequency` of
+    /// the CPU or else of `/cpus`.
+    pub timebase_frequency: Option<u64>,
+}
+
+impl DeviceTree {
+    /// The CPUs described by the children of `/cpus` with a `device_type`
+    /// of `cpu`, in document order.
+    ///
+    /// IDs are decoded using the `#address-cells` of `/cpus`. Other
+    /// children, such as `cpu-map`, and CPUs without a valid `reg` are
+    /// skipped. Disabled CPUs are included; see
+    /// [`cpu_count`](DeviceTree::cpu_count).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// let ids: Vec<u64> = dt.cpus().map(|cpu| cpu.id).collect();
+    /// assert_eq!(ids, [0xf00, 0xf01, 0xf02, 0xf03]);
+    /// ```
+    pub fn cpus(&self) -> impl Iterator<Item = Cpu<'_>> {
+        self.find("/cpus").into_iter().flat_map(|cpus| {
+            let timebase = frequency(cpus, "timebase-frequency");
+
+            cpus.children
+                .iter()
+                .filter(|node| node.property_str("device_type") == Some("cpu"))
+                .filter_map(move |node| {
+                    Some(Cpu {
+                        node,
+                        id: node.reg(cpus)?.next()?.address,
+                        compatible: node.property_str("compatible"),
+                        status: node.status(),
+                        enable_method: node.property_str("enable-method"),
+                        clock_frequency: frequency(node, "clock-frequency"),
+                        timebase_frequency: frequency(
+                            node,
+                            "timebase-frequency",
+                        )
+                        .or(timebase),
+                    })
+                })
+        })
+    }
+
+    /// The node of the CPU the system booted on, whose ID matches
+    /// [`boot_cpuid_phys`](DeviceTree::boot_cpuid_phys).
+    ///
+    /// If `/cpus` has an `#address-cells` of 2 and no ID matches exactly,
+    /// the low cell of the IDs is compared instead. Returns `None` if no CPU
+    /// matches.
+    pub fn boot_cpu(&self) -> Option<&Node> {
+        let boot_id = self.boot_cpuid_phys();
+        let cpu =
+            self.cpus()
+                .find(|cpu| cpu.id == boot_id as u64)
+                .or_else(|| {
+                    if self.find("/cpus")?.address_cells()? != 2 {
+                        return None;
+                    }
+                    self.cpus().find(|cpu| cpu.id as u32 == boot_id)
+                })?;
+
+        Some(cpu.node)
+    }
+
+    /// The number of enabled CPUs in [`cpus`](DeviceTree::cpus).
+    pub fn cpu_count(&self) -> usize {
+        self.cpus()
+            .filter(|cpu| cpu.status == NodeStatus::Okay)
+            .count()
+    }
+}
+
+/// A frequency, which may be 32 or 64 bits wide.
+fn frequency(node: &Node, name: &str) -> Option<u64> {
+    node.property(name)?.as_u64()
+}
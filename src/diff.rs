@@ -0,0 +1,177 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{child_path, split_path, DeviceTree, Node, Property};
+
+/// A single difference between two trees, found by [`DeviceTree::diff`].
+///
+/// Paths are absolute.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TreeDiff {
+    /// The node at this path only exists in the second tree. Its properties
+    /// and children follow as separate entries.
+    NodeAdded(String),
+
+    /// The node at this path, and everything below it, only exists in the
+    /// first tree.
+    NodeRemoved(String),
+
+    /// The value of a property differs between the trees.
+    PropertyChanged {
+        path: String,
+        name: String,
+        old: Vec<u8>,
+        new: Vec<u8>,
+    },
+
+    /// A property only exists in the second tree.
+    PropertyAdded {
+        path: String,
+        name: String,
+        value: Vec<u8>,
+    },
+
+    /// A property only exists in the first tree.
+    PropertyRemoved { path: String, name: String },
+}
+
+impl Node {
+    /// Record the differences between this node, at `path`, and `other`.
+    fn diff(&self, other: &Node, path: &str, diffs: &mut Vec<TreeDiff>) {
+        for prop in self.props.iter() {
+            match other.property(&prop.name) {
+                Some(new) if new.data == prop.data => {}
+                Some(new) => diffs.push(TreeDiff::PropertyChanged {
+                    path: path.into(),
+                    name: prop.name.clone(),
+                    old: prop.data.clone(),
+                    new: new.data.clone(),
+                }),
+                None => diffs.push(TreeDiff::PropertyRemoved {
+                    path: path.into(),
+                    name: prop.name.clone(),
+                }),
+            }
+        }
+        for prop in other.props.iter() {
+            if !self.has_property(&prop.name) {
+                diffs.push(TreeDiff::PropertyAdded {
+                    path: path.into(),
+                    name: prop.name.clone(),
+                    value: prop.data.clone(),
+                });
+            }
+        }
+
+        for child in self.children.iter() {
+            if other.find_child(&child.name).is_none() {
+                diffs
+                    .push(TreeDiff::NodeRemoved(child_path(path, &child.name)));
+            }
+        }
+        for child in other.children.iter() {
+            let child_path = child_path(path, &child.name);
+            match self.find_child(&child.name) {
+                Some(old) => old.diff(child, &child_path, diffs),
+                None => child.added(&child_path, diffs),
+            }
+        }
+    }
+
+    /// Record this node, at `path`, and everything below it as added.
+    fn added(&self, path: &str, diffs: &mut Vec<TreeDiff>) {
+        diffs.push(TreeDiff::NodeAdded(path.into()));
+        for prop in self.props.iter() {
+            diffs.push(TreeDiff::PropertyAdded {
+                path: path.into(),
+                name: prop.name.clone(),
+                value: prop.data.clone(),
+            });
+        }
+
+        for child in self.children.iter() {
+            child.added(&child_path(path, &child.name), diffs);
+        }
+    }
+}
+
+impl DeviceTree {
+    /// The differences between the nodes and properties of this tree and
+    /// `other`.
+    ///
+    /// The differences are listed in the order of the nodes in this tree,
+    /// followed by those that only exist in `other`, so the result is the
+    /// same for the same pair of trees. A node that was added is listed
+    /// before its properties and children. The memory reservations and the
+    /// header are not compared.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use flat_device_tree::{DeviceTreeBuilder, TreeDiff};
+    /// # let mut builder = DeviceTreeBuilder::new();
+    /// # builder.root("").child("uart@1000").property_str("status", "okay");
+    /// # let before = builder.build().unwrap();
+    /// # let mut builder = DeviceTreeBuilder::new();
+    /// # builder.root("").child("uart@1000").property_str("status", "disabled");
+    /// # let after = builder.build().unwrap();
+    /// let diff = before.diff(&after);
+    ///
+    /// assert_eq!(
+    ///     diff,
+    ///     [TreeDiff::PropertyChanged {
+    ///         path: "/uart@1000".into(),
+    ///         name: "status".into(),
+    ///         old: b"okay\0".to_vec(),
+    ///         new: b"disabled\0".to_vec(),
+    ///     }]
+    /// );
+    /// assert_eq!(before.apply_diff(&diff).unwrap().diff(&after), []);
+    /// ```
+    pub fn diff(&self, other: &DeviceTree) -> Vec<TreeDiff> {
+        let mut diffs = Vec::new();
+        self.root.diff(&other.root, "/", &mut diffs);
+        diffs
+    }
+
+    /// A copy of this tree with `diff`, as returned by
+    /// [`diff`](DeviceTree::diff), applied to it.
+    ///
+    /// Applying the differences to the first tree yields a tree with the
+    /// same nodes and properties as the second. Added properties and nodes
+    /// are appended, so their order may differ if the second tree was
+    /// reordered. Returns `None` if an entry refers to a node that does not
+    /// exist.
+    pub fn apply_diff(&self, diff: &[TreeDiff]) -> Option<DeviceTree> {
+        let mut tree = self.clone();
+
+        for entry in diff {
+            match entry {
+                TreeDiff::NodeAdded(path) => {
+                    let (parent, name) = split_path(path)?;
+                    let parent = tree.find_mut(parent)?;
+                    parent.children.push(Node::new(name));
+                }
+                TreeDiff::NodeRemoved(path) => {
+                    let (parent, name) = split_path(path)?;
+                    let parent = tree.find_mut(parent)?;
+                    parent.remove_child(name)?;
+                }
+                TreeDiff::PropertyChanged {
+                    path, name, new, ..
+                } => {
+                    let node = tree.find_mut(path)?;
+                    node.property_mut(name)?.data.clone_from(new);
+                }
+                TreeDiff::PropertyAdded { path, name, value } => {
+                    let node = tree.find_mut(path)?;
+                    node.props.push(Property::new(name, value.clone()));
+                }
+                TreeDiff::PropertyRemoved { path, name } => {
+                    tree.find_mut(path)?.remove_property(name);
+                }
+            }
+        }
+
+        Some(tree)
+    }
+}
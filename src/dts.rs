@@ -0,0 +1,126 @@
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+use crate::{DeviceTree, Node, Property, PropertyValue};
+
+impl DeviceTree {
+    /// Render the tree as device tree source, as accepted by `dtc -I dts`.
+    ///
+    /// Property values are printed as strings, cells or bytes depending on
+    /// what their contents look like. Labels and phandle references are not
+    /// recovered.
+    pub fn to_dts(&self) -> String {
+        let mut dts = String::new();
+        // writing to a String cannot fail
+        let _ = self.write_dts(&mut dts);
+        dts
+    }
+
+    fn write_dts(&self, out: &mut impl Write) -> fmt::Result {
+        writeln!(out, "/dts-v1/;")?;
+        writeln!(out)?;
+
+        for reservation in self.reserved.iter() {
+            writeln!(
+                out,
+                "/memreserve/ {:#x} {:#x};",
+                reservation.address, reservation.size
+            )?;
+        }
+        if !self.reserved.is_empty() {
+            writeln!(out)?;
+        }
+
+        write_node(out, &self.root, 0)
+    }
+}
+
+fn write_node(out: &mut impl Write, node: &Node, depth: usize) -> fmt::Result {
+    let name = if depth == 0 { "/" } else { node.name.as_str() };
+
+    indent(out, depth)?;
+    writeln!(out, "{} {{", name)?;
+
+    for prop in node.properties() {
+        indent(out, depth + 1)?;
+        write_property(out, prop)?;
+    }
+
+    for (i, child) in node.into_iter().enumerate() {
+        if i > 0 || !node.properties().is_empty() {
+            writeln!(out)?;
+        }
+        write_node(out, child, depth + 1)?;
+    }
+
+    indent(out, depth)?;
+    writeln!(out, "}};")
+}
+
+fn write_property(out: &mut impl Write, prop: &Property) -> fmt::Result {
+    let value = prop.value();
+    if value == PropertyValue::Empty {
+        return writeln!(out, "{};", prop.name);
+    }
+
+    write!(out, "{} = ", prop.name)?;
+
+    match value {
+        PropertyValue::Empty => {}
+        PropertyValue::String(s) => write_string(out, s)?,
+        PropertyValue::StringList(strings) => {
+            for (i, s) in strings.into_iter().enumerate() {
+                if i > 0 {
+                    write!(out, ", ")?;
+                }
+                write_string(out, s)?;
+            }
+        }
+        PropertyValue::U32(cell) | PropertyValue::Phandle(cell) => {
+            write!(out, "<{:#x}>", cell)?
+        }
+        PropertyValue::U64(value) => {
+            write!(out, "<{:#x} {:#x}>", value >> 32, value as u32)?
+        }
+        PropertyValue::Cells(cells) => {
+            write!(out, "<")?;
+            for (i, cell) in cells.into_iter().enumerate() {
+                if i > 0 {
+                    write!(out, " ")?;
+                }
+                write!(out, "{:#x}", cell)?;
+            }
+            write!(out, ">")?;
+        }
+        PropertyValue::Bytes(bytes) => {
+            write!(out, "[")?;
+            for (i, byte) in bytes.iter().enumerate() {
+                if i > 0 {
+                    write!(out, " ")?;
+                }
+                write!(out, "{:02x}", byte)?;
+            }
+            write!(out, "]")?;
+        }
+    }
+
+    writeln!(out, ";")
+}
+
+fn write_string(out: &mut impl Write, s: &str) -> fmt::Result {
+    write!(out, "\"")?;
+    for c in s.chars() {
+        if c == '"' || c == '\\' {
+            write!(out, "\\")?;
+        }
+        write!(out, "{}", c)?;
+    }
+    write!(out, "\"")
+}
+
+fn indent(out: &mut impl Write, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        write!(out, "\t")?;
+    }
+    Ok(())
+}
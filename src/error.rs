@@ -1,8 +1,22 @@
+use core::fmt;
+
 /// Convenience alias for the [`Result`](core::result::Result) type.
 pub type Result<T> = core::result::Result<T, Error>;
 
 pub type SliceReadResult<T> = core::result::Result<T, SliceReadError>;
 
+pub type NodeResult<T> = core::result::Result<T, NodeError>;
+
+pub type GraftResult<T> = core::result::Result<T, GraftError>;
+
+pub type PathResult<T> = core::result::Result<T, PathError>;
+
+pub type RenameResult<T> = core::result::Result<T, RenameError>;
+
+pub type TreeResult<T> = core::result::Result<T, TreeError>;
+
+pub type OverlayResult<T> = core::result::Result<T, OverlayError>;
+
 pub type VecWriteResult = core::result::Result<(), VecWriteError>;
 
 /// An error describe parsing problems when creating device trees.
@@ -16,6 +30,9 @@ pub enum Error {
     /// supplied to `load()`.
     SizeMismatch,
 
+    /// A block described by the header does not lie within `totalsize`.
+    InvalidHeader,
+
     /// Failed to read data from slice.
     SliceReadError(SliceReadError),
 
@@ -34,6 +51,9 @@ pub enum Error {
 
     /// Property could not be parsed
     PropError(PropError),
+
+    /// A node could not be constructed or modified
+    NodeError(NodeError),
 }
 
 impl From<SliceReadError> for Error {
@@ -54,6 +74,12 @@ impl From<PropError> for Error {
     }
 }
 
+impl From<NodeError> for Error {
+    fn from(e: NodeError) -> Self {
+        Self::NodeError(e)
+    }
+}
+
 impl From<core::str::Utf8Error> for Error {
     fn from(_: core::str::Utf8Error) -> Error {
         Error::Utf8Error
@@ -67,6 +93,32 @@ pub enum PropError {
     Utf8Error,
     Missing0,
     SliceReadError(SliceReadError),
+
+    /// The value is not made up of whole cells.
+    NotCellAligned,
+
+    /// No node has the given phandle.
+    UnknownPhandle(u32),
+
+    /// The node referred to lacks the property giving its number of
+    /// argument cells.
+    MissingCellCount,
+
+    /// The value ends in the middle of an entry.
+    Truncated,
+
+    /// Neither the node nor any of its ancestors has an
+    /// `interrupt-parent` property, and none of its ancestors is an
+    /// interrupt controller or nexus.
+    MissingInterruptParent,
+
+    /// A property giving a number of cells has a value that cannot be
+    /// used, such as `0` for `#interrupt-cells`, or a value does not have
+    /// the number of cells it should.
+    InvalidCellCount,
+
+    /// No entry of an `interrupt-map` matches the interrupt.
+    UnmappedInterrupt,
 }
 
 impl From<core::str::Utf8Error> for PropError {
@@ -81,9 +133,142 @@ impl From<SliceReadError> for PropError {
     }
 }
 
+/// Represents errors when building or modifying nodes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NodeError {
+    /// A node name is not of the form `node-name@unit-address`.
+    InvalidNodeName,
+
+    /// A property name is empty or not printable ASCII.
+    InvalidPropertyName,
+}
+
+/// Represents errors when parsing or extending a
+/// [`NodePath`](crate::NodePath).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PathError {
+    /// The path does not start with `/`.
+    NotAbsolute,
+
+    /// The path has an empty component, such as from `//` or a trailing
+    /// `/`.
+    EmptyComponent,
+
+    /// A component is not of the form `node-name@unit-address`.
+    InvalidComponent,
+}
+
+/// Represents errors when adding a node to a tree with
+/// [`DeviceTree::graft`](crate::DeviceTree::graft).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GraftError {
+    /// There is no node at the given parent path.
+    ParentNotFound,
+
+    /// The parent already has a child with the same name.
+    DuplicateChildName,
+
+    /// The name of the node or of a node below it is not of the form
+    /// `node-name@unit-address`.
+    InvalidNodeName,
+
+    /// A property name of the node or of a node below it is empty or not
+    /// printable ASCII.
+    InvalidPropertyName,
+}
+
+impl From<NodeError> for GraftError {
+    fn from(e: NodeError) -> GraftError {
+        match e {
+            NodeError::InvalidNodeName => GraftError::InvalidNodeName,
+            NodeError::InvalidPropertyName => GraftError::InvalidPropertyName,
+        }
+    }
+}
+
+/// Represents errors when renaming a node with
+/// [`DeviceTree::rename_node`](crate::DeviceTree::rename_node).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RenameError {
+    /// There is no node at the given path.
+    NodeNotFound,
+
+    /// The root node has no name to change.
+    RootNode,
+
+    /// The new name is not a valid node name without a unit address.
+    InvalidNodeName,
+
+    /// A sibling of the node already has the new name.
+    DuplicateChildName,
+}
+
+/// Represents errors when modifying a tree through the path of a node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TreeError {
+    /// There is no node at the given path.
+    NodeNotFound,
+
+    /// The node already has a child with the given name.
+    DuplicateChildName,
+
+    /// A node name is not of the form `node-name@unit-address`.
+    InvalidNodeName,
+
+    /// A property name is empty or not printable ASCII.
+    InvalidPropertyName,
+
+    /// Renumbering would move a phandle past the largest valid value.
+    PhandleOverflow,
+}
+
+impl From<NodeError> for TreeError {
+    fn from(e: NodeError) -> TreeError {
+        match e {
+            NodeError::InvalidNodeName => TreeError::InvalidNodeName,
+            NodeError::InvalidPropertyName => TreeError::InvalidPropertyName,
+        }
+    }
+}
+
+impl From<GraftError> for TreeError {
+    fn from(e: GraftError) -> TreeError {
+        match e {
+            GraftError::ParentNotFound => TreeError::NodeNotFound,
+            GraftError::DuplicateChildName => TreeError::DuplicateChildName,
+            GraftError::InvalidNodeName => TreeError::InvalidNodeName,
+            GraftError::InvalidPropertyName => TreeError::InvalidPropertyName,
+        }
+    }
+}
+
+/// Represents errors when applying an overlay to a tree.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OverlayError {
+    /// A fragment has an `__overlay__` node, but neither a `target` nor a
+    /// `target-path` property.
+    MissingTarget,
+
+    /// The node a fragment targets does not exist in the base tree.
+    TargetNotFound,
+
+    /// A label in `__fixups__` is not in the `__symbols__` of the base
+    /// tree, or the node it refers to has no phandle.
+    UnknownSymbol,
+
+    /// An entry of `__fixups__` or `__local_fixups__` is malformed or
+    /// refers to a location that does not exist in the overlay.
+    InvalidFixup,
+
+    /// The phandles of the overlay cannot be moved past those of the base
+    /// tree without running out of phandle values.
+    PhandleConflict,
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum SliceReadError {
-    UnexpectedEndOfInput,
+    /// The data read at the given offset extends past the end of the slice.
+    UnexpectedEndOfInput(usize),
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -91,3 +276,211 @@ pub enum VecWriteError {
     NonContiguousWrite,
     UnalignedWrite,
 }
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::InvalidMagicNumber => {
+                f.write_str("invalid FDT magic number")
+            }
+            Error::SizeMismatch => {
+                f.write_str("device tree size does not match the buffer")
+            }
+            Error::InvalidHeader => {
+                f.write_str("header block outside of the device tree")
+            }
+            Error::SliceReadError(_) => {
+                f.write_str("failed to read device tree data")
+            }
+            Error::ParseError(pos) => {
+                write!(f, "unexpected data at offset {:#x}", pos)
+            }
+            Error::Utf8Error => f.write_str("invalid UTF-8 in string"),
+            Error::VersionNotSupported => {
+                f.write_str("unsupported device tree version")
+            }
+            Error::VecWriteError(_) => {
+                f.write_str("failed to write device tree data")
+            }
+            Error::PropError(_) => f.write_str("invalid property"),
+            Error::NodeError(_) => f.write_str("invalid node"),
+        }
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::SliceReadError(e) => Some(e),
+            Error::VecWriteError(e) => Some(e),
+            Error::PropError(e) => Some(e),
+            Error::NodeError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PropError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PropError::NotFound => f.write_str("property not found"),
+            PropError::Utf8Error => f.write_str("invalid UTF-8 in string"),
+            PropError::Missing0 => f.write_str("string is not NUL-terminated"),
+            PropError::SliceReadError(_) => {
+                f.write_str("failed to read property value")
+            }
+            PropError::NotCellAligned => {
+                f.write_str("value is not a whole number of cells")
+            }
+            PropError::UnknownPhandle(phandle) => {
+                write!(f, "no node with phandle {:#x}", phandle)
+            }
+            PropError::MissingCellCount => {
+                f.write_str("referenced node has no cell count")
+            }
+            PropError::Truncated => f.write_str("value ends mid-entry"),
+            PropError::MissingInterruptParent => {
+                f.write_str("no interrupt parent")
+            }
+            PropError::InvalidCellCount => f.write_str("invalid cell count"),
+            PropError::UnmappedInterrupt => {
+                f.write_str("interrupt not found in interrupt-map")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PropError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            PropError::SliceReadError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for NodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NodeError::InvalidNodeName => f.write_str("invalid node name"),
+            NodeError::InvalidPropertyName => {
+                f.write_str("invalid property name")
+            }
+        }
+    }
+}
+
+impl core::error::Error for NodeError {}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PathError::NotAbsolute => f.write_str("path is not absolute"),
+            PathError::EmptyComponent => {
+                f.write_str("path has an empty component")
+            }
+            PathError::InvalidComponent => {
+                f.write_str("path has an invalid node name")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PathError {}
+
+impl fmt::Display for GraftError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GraftError::ParentNotFound => f.write_str("parent node not found"),
+            GraftError::DuplicateChildName => {
+                f.write_str("parent already has a child of that name")
+            }
+            GraftError::InvalidNodeName => f.write_str("invalid node name"),
+            GraftError::InvalidPropertyName => {
+                f.write_str("invalid property name")
+            }
+        }
+    }
+}
+
+impl core::error::Error for GraftError {}
+
+impl fmt::Display for RenameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenameError::NodeNotFound => f.write_str("node not found"),
+            RenameError::RootNode => f.write_str("cannot rename the root node"),
+            RenameError::InvalidNodeName => f.write_str("invalid node name"),
+            RenameError::DuplicateChildName => {
+                f.write_str("a sibling already has that name")
+            }
+        }
+    }
+}
+
+impl core::error::Error for RenameError {}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TreeError::NodeNotFound => f.write_str("node not found"),
+            TreeError::DuplicateChildName => {
+                f.write_str("node already has a child of that name")
+            }
+            TreeError::InvalidNodeName => f.write_str("invalid node name"),
+            TreeError::InvalidPropertyName => {
+                f.write_str("invalid property name")
+            }
+            TreeError::PhandleOverflow => f.write_str("phandle out of range"),
+        }
+    }
+}
+
+impl core::error::Error for TreeError {}
+
+impl fmt::Display for OverlayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OverlayError::MissingTarget => {
+                f.write_str("overlay fragment has no target")
+            }
+            OverlayError::TargetNotFound => {
+                f.write_str("overlay target not found in base tree")
+            }
+            OverlayError::UnknownSymbol => {
+                f.write_str("overlay refers to an unknown symbol")
+            }
+            OverlayError::InvalidFixup => f.write_str("invalid overlay fixup"),
+            OverlayError::PhandleConflict => {
+                f.write_str("overlay phandles out of range")
+            }
+        }
+    }
+}
+
+impl core::error::Error for OverlayError {}
+
+impl fmt::Display for SliceReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SliceReadError::UnexpectedEndOfInput(pos) => {
+                write!(f, "unexpected end of input at offset {:#x}", pos)
+            }
+        }
+    }
+}
+
+impl core::error::Error for SliceReadError {}
+
+impl fmt::Display for VecWriteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VecWriteError::NonContiguousWrite => {
+                f.write_str("write past the end of the buffer")
+            }
+            VecWriteError::UnalignedWrite => f.write_str("unaligned write"),
+        }
+    }
+}
+
+impl core::error::Error for VecWriteError {}
@@ -0,0 +1,96 @@
+//! Wildcard matching of node paths.
+//!
+//! A pattern is an absolute path whose components may contain `*`, which
+//! matches any run of characters within a single component. A final `**`
+//! component matches every node below the preceding components, at any
+//! depth. Anywhere else `**` behaves like `*`.
+
+use alloc::{vec, vec::Vec};
+
+use crate::Node;
+
+/// Match a single path component against a pattern component.
+///
+/// `*` matches any (possibly empty) run of characters, everything else
+/// matches literally.
+pub fn component_matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.as_bytes();
+    let name = name.as_bytes();
+
+    let (mut p, mut n) = (0, 0);
+    // position of the last `*` in the pattern and the name position it was
+    // tried at, to backtrack to when a literal match fails
+    let mut backtrack = None;
+
+    while n < name.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if p < pattern.len() && pattern[p] == name[n] {
+            p += 1;
+            n += 1;
+        } else if let Some((star, pos)) = backtrack {
+            // let the last `*` swallow one more character
+            p = star + 1;
+            n = pos + 1;
+            backtrack = Some((star, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+/// Iterator over the nodes matching a path pattern, in document order.
+///
+/// Created by [`DeviceTree::find_nodes_glob`](crate::DeviceTree::find_nodes_glob)
+/// or [`Glob::new`].
+pub struct Glob<'a, 'p> {
+    pattern: Vec<&'p str>,
+    // nodes still to visit, along with the number of pattern components they
+    // have matched so far
+    stack: Vec<(&'a Node, usize)>,
+}
+
+impl<'a, 'p> Glob<'a, 'p> {
+    /// Match `pattern` against the tree rooted at `root`.
+    pub fn new(root: &'a Node, pattern: &'p str) -> Glob<'a, 'p> {
+        let pattern = pattern.split('/').filter(|c| !c.is_empty()).collect();
+
+        Glob {
+            pattern,
+            stack: vec![(root, 0)],
+        }
+    }
+}
+
+impl<'a> Iterator for Glob<'a, '_> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        while let Some((node, matched)) = self.stack.pop() {
+            let component = match self.pattern.get(matched) {
+                Some(component) => *component,
+                None => {
+                    // a trailing `**` keeps matching further down
+                    if matched > 0 && self.pattern[matched - 1] == "**" {
+                        for child in node.children.iter().rev() {
+                            self.stack.push((child, matched));
+                        }
+                    }
+
+                    return Some(node);
+                }
+            };
+
+            for child in node.children.iter().rev() {
+                if component_matches(component, &child.name) {
+                    self.stack.push((child, matched + 1));
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,84 @@
+use alloc::{format, vec, vec::Vec};
+
+use crate::{DeviceTree, Node, PropError, Result};
+
+/// Flag in the second cell of a GPIO specifier marking the line as active
+/// low, as defined by `dt-bindings/gpio/gpio.h`.
+const GPIO_ACTIVE_LOW: u32 = 1;
+
+/// A GPIO line used by a device, from a `gpios` or `*-gpios` property.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Gpio<'a> {
+    /// The GPIO controller.
+    pub controller: &'a Node,
+
+    /// The specifier cells, `#gpio-cells` of the controller long. These are
+    /// usually the line number followed by flags.
+    pub args: Vec<u32>,
+
+    /// The name of the line from `gpio-names`, if there is one.
+    pub name: Option<&'a str>,
+}
+
+impl Gpio<'_> {
+    /// The line number on the controller, from the first specifier cell.
+    pub fn line(&self) -> Option<u32> {
+        self.args.first().copied()
+    }
+
+    /// The flags from the second specifier cell. Controllers with a single
+    /// cell have no flags, so this is `0` for them.
+    pub fn flags(&self) -> u32 {
+        self.args.get(1).copied().unwrap_or(0)
+    }
+
+    /// Whether the line is active low.
+    pub fn is_active_low(&self) -> bool {
+        self.flags() & GPIO_ACTIVE_LOW != 0
+    }
+}
+
+impl Node {
+    /// The GPIO lines listed in the property `prop`, split using the
+    /// `#gpio-cells` of each controller.
+    ///
+    /// `prop` may be the full property name, such as `gpios` or `cd-gpios`,
+    /// or the function alone, such as `cd`, in which case `cd-gpios` and
+    /// then the deprecated `cd-gpio` are tried. A property named after the
+    /// function alone, such as `cd`, is never read. Lines of the `gpios`
+    /// property are paired with the entries of `gpio-names`.
+    pub fn gpios<'a>(
+        &'a self,
+        tree: &'a DeviceTree,
+        prop: &str,
+    ) -> Result<Vec<Gpio<'a>>> {
+        let names = if prop == "gpios"
+            || prop.ends_with("-gpios")
+            || prop.ends_with("-gpio")
+        {
+            vec![prop.into()]
+        } else {
+            vec![format!("{}-gpios", prop), format!("{}-gpio", prop)]
+        };
+        let name = names
+            .into_iter()
+            .find(|name| self.has_property(name))
+            .ok_or(PropError::NotFound)?;
+
+        let mut names = self
+            .property("gpio-names")
+            .filter(|_| name == "gpios")
+            .into_iter()
+            .flat_map(|prop| prop.strings());
+
+        Ok(tree
+            .parse_phandle_args(self, &name, "#gpio-cells")?
+            .into_iter()
+            .map(|entry| Gpio {
+                controller: entry.target,
+                args: entry.args,
+                name: names.next(),
+            })
+            .collect())
+    }
+}
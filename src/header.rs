@@ -0,0 +1,134 @@
+use crate::util::SliceRead;
+use crate::{Error, Result, COMPAT_VERSION, MAGIC_NUMBER, SUPPORTED_VERSION};
+
+/// Header of a flattened device tree blob.
+///
+/// All offsets are relative to the start of the blob.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceTreeHeader {
+    /// Magic number, always `0xd00dfeed`.
+    pub magic: u32,
+
+    /// Total size of the blob in bytes.
+    pub totalsize: u32,
+
+    /// Offset of the structure block.
+    pub off_dt_struct: u32,
+
+    /// Offset of the strings block.
+    pub off_dt_strings: u32,
+
+    /// Offset of the memory reservation block.
+    pub off_mem_rsvmap: u32,
+
+    /// Version of the blob format.
+    pub version: u32,
+
+    /// Lowest version the blob is backwards compatible with.
+    pub last_comp_version: u32,
+
+    /// Physical ID of the CPU the system boots from.
+    pub boot_cpuid_phys: u32,
+
+    /// Size of the strings block in bytes.
+    pub size_dt_strings: u32,
+
+    /// Size of the structure block in bytes.
+    pub size_dt_struct: u32,
+}
+
+impl Default for DeviceTreeHeader {
+    fn default() -> Self {
+        DeviceTreeHeader {
+            magic: MAGIC_NUMBER,
+            totalsize: 0,
+            off_dt_struct: 0,
+            off_dt_strings: 0,
+            off_mem_rsvmap: 0,
+            version: SUPPORTED_VERSION,
+            last_comp_version: COMPAT_VERSION,
+            boot_cpuid_phys: 0,
+            size_dt_strings: 0,
+            size_dt_struct: 0,
+        }
+    }
+}
+
+impl DeviceTreeHeader {
+    /// The size of the header in bytes.
+    pub const SIZE: usize = 40;
+
+    /// Read only the header at the start of a blob, e.g. to learn
+    /// `totalsize` before copying the blob.
+    ///
+    /// Only the first [`SIZE`](DeviceTreeHeader::SIZE) bytes are needed.
+    /// Apart from the magic number, the fields are not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTreeHeader;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let header = DeviceTreeHeader::load(&buf[..DeviceTreeHeader::SIZE]);
+    ///
+    /// assert_eq!(header.unwrap().totalsize as usize, buf.len());
+    /// ```
+    pub fn load(buffer: &[u8]) -> Result<DeviceTreeHeader> {
+        //  0  magic_number: u32,
+
+        //  4  totalsize: u32,
+        //  8  off_dt_struct: u32,
+        // 12  off_dt_strings: u32,
+        // 16  off_mem_rsvmap: u32,
+        // 20  version: u32,
+        // 24  last_comp_version: u32,
+
+        // // version 2 fields
+        // 28  boot_cpuid_phys: u32,
+
+        // // version 3 fields
+        // 32  size_dt_strings: u32,
+
+        // // version 17 fields
+        // 36  size_dt_struct: u32,
+
+        let magic = buffer.read_be_u32(0)?;
+        if magic != MAGIC_NUMBER {
+            return Err(Error::InvalidMagicNumber);
+        }
+
+        Ok(DeviceTreeHeader {
+            magic,
+            totalsize: buffer.read_be_u32(4)?,
+            off_dt_struct: buffer.read_be_u32(8)?,
+            off_dt_strings: buffer.read_be_u32(12)?,
+            off_mem_rsvmap: buffer.read_be_u32(16)?,
+            version: buffer.read_be_u32(20)?,
+            last_comp_version: buffer.read_be_u32(24)?,
+            boot_cpuid_phys: buffer.read_be_u32(28)?,
+            size_dt_strings: buffer.read_be_u32(32)?,
+            size_dt_struct: buffer.read_be_u32(36)?,
+        })
+    }
+
+    /// Check that the blocks described by the header lie within
+    /// `totalsize`.
+    pub(crate) fn validate(&self) -> Result<()> {
+        let totalsize = self.totalsize as u64;
+        let block_end = |offset: u32, size: u32| offset as u64 + size as u64;
+
+        if self.off_dt_struct as u64 >= totalsize
+            // dtc puts an empty strings block right at the end of the blob
+            || self.off_dt_strings as u64 > totalsize
+            || self.off_mem_rsvmap as u64 >= totalsize
+            || block_end(self.off_dt_strings, self.size_dt_strings) > totalsize
+            || block_end(self.off_dt_struct, self.size_dt_struct) > totalsize
+        {
+            return Err(Error::InvalidHeader);
+        }
+
+        Ok(())
+    }
+}
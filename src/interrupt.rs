@@ -0,0 +1,216 @@
+use alloc::{vec, vec::Vec};
+
+use crate::phandle::walk_phandle_list;
+use crate::{DeviceTree, Node, PropError, Result};
+
+/// An interrupt of a device, as a controller and the specifier cells that
+/// identify the interrupt to that controller.
+///
+/// The meaning of the cells depends on the binding of the controller.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterruptSpecifier<'a> {
+    /// The interrupt controller the interrupt is delivered to.
+    pub controller: &'a Node,
+
+    /// The specifier cells, `#interrupt-cells` of the controller long.
+    pub cells: Vec<u32>,
+}
+
+impl DeviceTree {
+    /// The interrupt controller, or nexus, for the interrupts of `node`.
+    ///
+    /// This is the node referred to by the `interrupt-parent` property of
+    /// `node`. Without one, it is the parent of `node` if that is an
+    /// interrupt controller or has an `interrupt-map`, and otherwise the
+    /// interrupt parent of the parent, found the same way. This is how
+    /// Linux resolves it, following the Devicetree specification.
+    ///
+    /// If `node` is not part of this tree, only its own property is
+    /// considered.
+    pub fn interrupt_parent<'a>(&'a self, node: &'a Node) -> Result<&'a Node> {
+        let lineage = self.lineage(node).unwrap_or_else(|| vec![node]);
+
+        for (depth, node) in lineage.iter().enumerate().rev() {
+            if let Some(prop) = node.property("interrupt-parent") {
+                let phandle = prop.as_u32().ok_or(PropError::NotCellAligned)?;
+                return Ok(self
+                    .find_by_phandle(phandle)
+                    .ok_or(PropError::UnknownPhandle(phandle))?);
+            }
+
+            let parent = depth.checked_sub(1).map(|depth| lineage[depth]);
+            if let Some(parent) = parent.filter(|parent| {
+                parent.has_property("interrupt-controller")
+                    || parent.has_property("interrupt-map")
+            }) {
+                return Ok(parent);
+            }
+        }
+
+        Err(PropError::MissingInterruptParent.into())
+    }
+
+    /// Split the `interrupts` property of `node` into the specifiers of
+    /// its interrupts, using the `#interrupt-cells` of its
+    /// [interrupt parent](DeviceTree::interrupt_parent).
+    ///
+    /// Returns an empty list if `node` has no `interrupts` property.
+    pub fn interrupts<'a>(
+        &'a self,
+        node: &'a Node,
+    ) -> Result<Vec<InterruptSpecifier<'a>>> {
+        let prop = match node.property("interrupts") {
+            Some(prop) => prop,
+            None => return Ok(Vec::new()),
+        };
+        if !prop.is_cell_aligned() {
+            return Err(PropError::NotCellAligned.into());
+        }
+
+        let controller = self.interrupt_parent(node)?;
+        let count = controller
+            .property_u32("#interrupt-cells")
+            .ok_or(PropError::MissingCellCount)? as usize;
+        if count == 0 {
+            return Err(PropError::InvalidCellCount.into());
+        }
+
+        let cells: Vec<u32> = prop.cells().collect();
+        if !cells.len().is_multiple_of(count) {
+            return Err(PropError::Truncated.into());
+        }
+
+        Ok(cells
+            .chunks_exact(count)
+            .map(|cells| InterruptSpecifier {
+                controller,
+                cells: cells.to_vec(),
+            })
+            .collect())
+    }
+
+    /// Decode the `interrupts-extended` property of `node`, which lists
+    /// interrupts with their own controllers, as in
+    /// `interrupts-extended = <&clint 3>, <&plic 9>`.
+    ///
+    /// Returns an empty list if `node` has no `interrupts-extended`
+    /// property.
+    pub fn interrupts_extended<'a>(
+        &'a self,
+        node: &Node,
+    ) -> Result<Vec<InterruptSpecifier<'a>>> {
+        let prop = match node.property("interrupts-extended") {
+            Some(prop) => prop,
+            None => return Ok(Vec::new()),
+        };
+        if !prop.is_cell_aligned() {
+            return Err(PropError::NotCellAligned.into());
+        }
+
+        let cells: Vec<u32> = prop.cells().collect();
+        let mut irqs = Vec::new();
+        walk_phandle_list(
+            &cells,
+            Some("#interrupt-cells"),
+            |phandle| self.find_by_phandle(phandle),
+            |_, _, controller, cells| {
+                irqs.push(InterruptSpecifier {
+                    controller,
+                    cells: cells.to_vec(),
+                })
+            },
+        )?;
+
+        Ok(irqs)
+    }
+
+    /// Map an interrupt of a child of `nexus`, such as a PCI host bridge,
+    /// through the `interrupt-map` of `nexus` to its parent controller.
+    ///
+    /// `child_unit_addr` must be `#address-cells` and `child_spec`
+    /// `#interrupt-cells` of `nexus` long. Both are masked with
+    /// `interrupt-map-mask`, if present, before looking them up. The parent
+    /// unit address of the matching entry is dropped, so the result only
+    /// holds the parent's interrupt specifier.
+    pub fn map_interrupt<'a>(
+        &'a self,
+        nexus: &Node,
+        child_unit_addr: &[u32],
+        child_spec: &[u32],
+    ) -> Result<InterruptSpecifier<'a>> {
+        let map = nexus.property("interrupt-map").ok_or(PropError::NotFound)?;
+        if !map.is_cell_aligned() {
+            return Err(PropError::NotCellAligned.into());
+        }
+
+        let address_cells =
+            nexus.address_cells().ok_or(PropError::InvalidCellCount)?;
+        let interrupt_cells = nexus
+            .property_u32("#interrupt-cells")
+            .ok_or(PropError::MissingCellCount)?;
+        if child_unit_addr.len() != address_cells as usize
+            || child_spec.len() != interrupt_cells as usize
+        {
+            return Err(PropError::InvalidCellCount.into());
+        }
+
+        let mut key: Vec<u32> =
+            child_unit_addr.iter().chain(child_spec).copied().collect();
+        let mask: Vec<u32> = match nexus.property("interrupt-map-mask") {
+            Some(mask) => mask.cells().collect(),
+            None => vec![!0; key.len()],
+        };
+        if mask.len() != key.len() {
+            return Err(PropError::InvalidCellCount.into());
+        }
+        mask_cells(&mut key, &mask);
+
+        // walk the whole map, so malformed maps are reported even if an
+        // earlier entry matches
+        let cells: Vec<u32> = map.cells().collect();
+        let mut rest = cells.as_slice();
+        let mut found = None;
+        while !rest.is_empty() {
+            if rest.len() <= key.len() {
+                return Err(PropError::Truncated.into());
+            }
+            let (child, tail) = rest.split_at(key.len());
+            let (phandle, tail) = (tail[0], &tail[1..]);
+
+            let parent = self
+                .find_by_phandle(phandle)
+                .ok_or(PropError::UnknownPhandle(phandle))?;
+            // unlike elsewhere, a missing #address-cells means 0 here
+            let parent_address_cells =
+                parent.property_u32("#address-cells").unwrap_or(0) as usize;
+            let parent_interrupt_cells = parent
+                .property_u32("#interrupt-cells")
+                .ok_or(PropError::MissingCellCount)?
+                as usize;
+            let len = parent_address_cells + parent_interrupt_cells;
+            if tail.len() < len {
+                return Err(PropError::Truncated.into());
+            }
+            let (parent_cells, tail) = tail.split_at(len);
+
+            let mut child = child.to_vec();
+            mask_cells(&mut child, &mask);
+            if found.is_none() && child == key {
+                found = Some(InterruptSpecifier {
+                    controller: parent,
+                    cells: parent_cells[parent_address_cells..].to_vec(),
+                });
+            }
+
+            rest = tail;
+        }
+
+        Ok(found.ok_or(PropError::UnmappedInterrupt)?)
+    }
+}
+
+fn mask_cells(cells: &mut [u32], mask: &[u32]) {
+    for (cell, mask) in cells.iter_mut().zip(mask) {
+        *cell &= mask;
+    }
+}
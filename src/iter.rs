@@ -0,0 +1,223 @@
+//! Iterators over the nodes of a tree.
+//!
+//! The iterators keep an explicit stack or queue instead of recursing, so
+//! they work on arbitrarily deep trees.
+
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+use core::slice;
+
+use crate::{Node, Property};
+
+/// Depth-first (pre-order) iterator yielding each node with its depth.
+///
+/// The node the iteration starts at has depth `0`.
+pub struct DfsWithDepth<'a> {
+    root: Option<&'a Node>,
+    // the remaining children of every node on the path to the current one
+    stack: Vec<slice::Iter<'a, Node>>,
+}
+
+impl<'a> DfsWithDepth<'a> {
+    /// Iterate over `root` and all of its descendants.
+    pub fn new(root: &'a Node) -> DfsWithDepth<'a> {
+        DfsWithDepth {
+            root: Some(root),
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl<'a> Iterator for DfsWithDepth<'a> {
+    type Item = (usize, &'a Node);
+
+    fn next(&mut self) -> Option<(usize, &'a Node)> {
+        if let Some(root) = self.root.take() {
+            self.stack.push(root.children.iter());
+            return Some((0, root));
+        }
+
+        while let Some(siblings) = self.stack.last_mut() {
+            match siblings.next() {
+                Some(node) => {
+                    let depth = self.stack.len();
+                    self.stack.push(node.children.iter());
+                    return Some((depth, node));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // every pending sibling is yielded, but their descendants are not
+        // known without walking them
+        let pending: usize = self.stack.iter().map(|s| s.len()).sum();
+        (pending + self.root.is_some() as usize, None)
+    }
+}
+
+/// Depth-first (pre-order) iterator over nodes.
+pub struct Dfs<'a>(DfsWithDepth<'a>);
+
+impl<'a> Dfs<'a> {
+    /// Iterate over `root` and all of its descendants.
+    pub fn new(root: &'a Node) -> Dfs<'a> {
+        Dfs(DfsWithDepth::new(root))
+    }
+}
+
+impl<'a> Iterator for Dfs<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        self.0.next().map(|(_, node)| node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+/// Depth-first (pre-order) iterator yielding each node with its absolute
+/// path, e.g. `/soc/uart@10000000`.
+///
+/// The path is built up while walking the tree, so the path of the parent is
+/// not looked up again for every node.
+pub struct DfsWithPaths<'a> {
+    root: Option<&'a Node>,
+    // the length of the path of every node on the path to the current one,
+    // along with its remaining children
+    stack: Vec<(usize, slice::Iter<'a, Node>)>,
+    path: String,
+}
+
+impl<'a> DfsWithPaths<'a> {
+    /// Iterate over `root`, which has the path `/`, and all of its
+    /// descendants.
+    pub fn new(root: &'a Node) -> DfsWithPaths<'a> {
+        DfsWithPaths {
+            root: Some(root),
+            stack: Vec::new(),
+            path: String::new(),
+        }
+    }
+}
+
+impl<'a> Iterator for DfsWithPaths<'a> {
+    type Item = (String, &'a Node);
+
+    fn next(&mut self) -> Option<(String, &'a Node)> {
+        if let Some(root) = self.root.take() {
+            self.path.push('/');
+            self.stack.push((0, root.children.iter()));
+            return Some((self.path.clone(), root));
+        }
+
+        while let Some((len, siblings)) = self.stack.last_mut() {
+            match siblings.next() {
+                Some(node) => {
+                    self.path.truncate(*len);
+                    self.path.push('/');
+                    self.path.push_str(&node.name);
+                    self.stack.push((self.path.len(), node.children.iter()));
+                    return Some((self.path.clone(), node));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let pending: usize = self.stack.iter().map(|(_, s)| s.len()).sum();
+        (pending + self.root.is_some() as usize, None)
+    }
+}
+
+/// Iterator over every property of a tree, along with the absolute path of
+/// the node it belongs to.
+///
+/// The properties of a node are yielded in order, before those of its
+/// children, and the nodes are visited in depth-first (document) order.
+pub struct PropertiesWithPaths<'a> {
+    nodes: DfsWithPaths<'a>,
+    // the path of the current node and its remaining properties
+    current: Option<(String, slice::Iter<'a, Property>)>,
+}
+
+impl<'a> PropertiesWithPaths<'a> {
+    /// Iterate over the properties of `root`, which has the path `/`, and
+    /// of all of its descendants.
+    pub fn new(root: &'a Node) -> PropertiesWithPaths<'a> {
+        PropertiesWithPaths {
+            nodes: DfsWithPaths::new(root),
+            current: None,
+        }
+    }
+}
+
+impl<'a> Iterator for PropertiesWithPaths<'a> {
+    type Item = (String, &'a Property);
+
+    fn next(&mut self) -> Option<(String, &'a Property)> {
+        loop {
+            if let Some((path, props)) = &mut self.current {
+                if let Some(prop) = props.next() {
+                    return Some((path.clone(), prop));
+                }
+            }
+
+            let (path, node) = self.nodes.next()?;
+            self.current = Some((path, node.props.iter()));
+        }
+    }
+}
+
+/// Breadth-first iterator over nodes, yielding them level by level.
+pub struct Bfs<'a> {
+    // pending nodes along with their depth
+    queue: VecDeque<(usize, &'a Node)>,
+    max_depth: Option<usize>,
+}
+
+impl<'a> Bfs<'a> {
+    /// Iterate over `root` and all of its descendants.
+    pub fn new(root: &'a Node) -> Bfs<'a> {
+        Bfs::with_max_depth(root, None)
+    }
+
+    /// Iterate over `root` and its descendants down to `max_depth`, if
+    /// given. `root` has depth `0`, so a `max_depth` of `Some(0)` only
+    /// yields `root` itself.
+    pub fn with_max_depth(root: &'a Node, max_depth: Option<usize>) -> Bfs<'a> {
+        let mut queue = VecDeque::new();
+        queue.push_back((0, root));
+
+        Bfs { queue, max_depth }
+    }
+}
+
+impl<'a> Iterator for Bfs<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        let (depth, node) = self.queue.pop_front()?;
+        if self.max_depth.is_none_or(|max| depth < max) {
+            let children = node.children.iter().map(|child| (depth + 1, child));
+            self.queue.extend(children);
+        }
+
+        Some(node)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), None)
+    }
+}
@@ -26,7 +26,7 @@
 //!     let mut buf = Vec::new();
 //!     input.read_to_end(&mut buf).unwrap();
 //!
-//!     let dt = device_tree::DeviceTree::load(buf.as_slice ()).unwrap();
+//!     let dt = flat_device_tree::DeviceTree::load(buf.as_slice ()).unwrap();
 //!     println!("{:?}", dt);
 //! }
 //! ```
@@ -36,36 +36,81 @@
 extern crate alloc;
 extern crate hashbrown;
 
+mod address;
+mod alias;
+mod borrowed;
+mod builder;
+mod chosen;
+mod clock;
+mod cpu;
+mod diff;
+mod dts;
 mod error;
+pub mod glob;
+mod gpio;
+mod header;
+mod interrupt;
+pub mod iter;
+mod memory;
+mod name;
+mod overlay;
+mod path;
+mod phandle;
+mod property;
+mod stats;
+mod status;
+mod stream;
 pub mod util;
+mod validate;
+pub mod visit;
 
 use alloc::{borrow::ToOwned, string::String, vec::Vec};
-use core::str;
-
+use core::{slice, str};
+
+pub use address::{AddressRange, RegEntry};
+pub use borrowed::{BorrowedNode, BorrowedProperty, BorrowedTree};
+pub use builder::{DeviceTreeBuilder, NodeBuilder};
+pub use chosen::{Chosen, Parity, SerialOptions};
+pub use clock::Clock;
+pub use cpu::Cpu;
+pub use diff::TreeDiff;
 pub use error::*;
-use util::{align, SliceRead, VecWrite};
+use glob::Glob;
+pub use gpio::Gpio;
+pub use header::DeviceTreeHeader;
+pub use interrupt::InterruptSpecifier;
+use iter::{Bfs, Dfs, DfsWithDepth, DfsWithPaths, PropertiesWithPaths};
+pub use memory::{MemoryRegion, ReservedMemory};
+use name::{is_valid_node_name, is_valid_property_name, validate_node};
+pub use path::{NodePath, NodeRef, TreeEntry};
+pub use phandle::{
+    PhandleArgs, PhandleIndex, PhandleMap, Reference, ReferenceIndex,
+};
+pub use property::{
+    Cells, PropHint, PropValue, Property, PropertyValue, Strings,
+};
+pub use stats::TreeStats;
+pub use status::NodeStatus;
+pub use stream::{ParseEvent, StreamingParser};
+use util::{SliceRead, VecWrite};
+pub use validate::ValidationWarning;
 
-#[cfg(not(feature = "string-dedup"))]
 mod string_table;
-#[cfg(feature = "string-dedup")]
-mod advanced_string_table;
 
-#[cfg(not(feature = "string-dedup"))]
 use string_table::StringTable;
 
-#[cfg(feature = "string-dedup")]
-use advanced_string_table::StringTable;
-
 const MAGIC_NUMBER: u32 = 0xd00dfeed;
 const SUPPORTED_VERSION: u32 = 17;
 const COMPAT_VERSION: u32 = 16;
 const OF_DT_BEGIN_NODE: u32 = 0x00000001;
 const OF_DT_END_NODE: u32 = 0x00000002;
 const OF_DT_PROP: u32 = 0x00000003;
+const OF_DT_NOP: u32 = 0x00000004;
 const OF_DT_END: u32 = 0x00000009;
 
 /// Device tree structure.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DeviceTree {
     /// Version, as indicated by version header
     pub version: u32,
@@ -73,93 +118,162 @@ pub struct DeviceTree {
     /// The number of the CPU the system boots from
     pub boot_cpuid_phys: u32,
 
-    /// A list of tuples of `(offset, length)`, indicating reserved memory
-    // regions.
-    pub reserved: Vec<(u64, u64)>,
+    /// Memory regions reserved through the memory reservation block.
+    pub reserved: Vec<MemoryReservation>,
 
     /// The root node.
     pub root: Node,
+
+    /// Header of the blob the tree was loaded from.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    header: DeviceTreeHeader,
+}
+
+// The header only describes the layout of the blob a tree was loaded from,
+// so two trees with the same contents compare equal regardless of it.
+impl PartialEq for DeviceTree {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version
+            && self.boot_cpuid_phys == other.boot_cpuid_phys
+            && self.reserved == other.reserved
+            && self.root == other.root
+    }
+}
+
+impl Eq for DeviceTree {}
+
+/// An entry of the memory reservation block.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryReservation {
+    /// Physical address of the reserved region.
+    pub address: u64,
+
+    /// Size of the reserved region in bytes.
+    pub size: u64,
 }
 
 /// A single node in the device tree.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
     /// The name of the node, as it appears in the node path.
-    pub name: String,
+    pub(crate) name: String,
 
-    /// A list of node properties, `(key, value)`.
-    pub props: Vec<(String, Vec<u8>)>,
+    /// A list of node properties.
+    pub(crate) props: Vec<Property>,
 
     /// Child nodes of this node.
-    pub children: Vec<Node>,
+    pub(crate) children: Vec<Node>,
+
+    /// Offset of the node in the blob it was loaded from.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) struct_offset: Option<usize>,
+}
+
+// Like the header of a tree, where a node was loaded from does not affect
+// whether it is equal to another.
+impl PartialEq for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.props == other.props
+            && self.children == other.children
+    }
 }
 
+impl Eq for Node {}
+
 impl DeviceTree {
     //! Load a device tree from a memory buffer.
-    pub fn load(buffer: &[u8]) -> Result<DeviceTree> {
-        //  0  magic_number: u32,
 
-        //  4  totalsize: u32,
-        //  8  off_dt_struct: u32,
-        // 12  off_dt_strings: u32,
-        // 16  off_mem_rsvmap: u32,
-        // 20  version: u32,
-        // 24  last_comp_version: u32,
+    /// Create a device tree with the given root node and no memory
+    /// reservations.
+    pub fn new(root: Node) -> DeviceTree {
+        DeviceTree {
+            version: SUPPORTED_VERSION,
+            boot_cpuid_phys: 0,
+            reserved: Vec::new(),
+            root,
+            header: DeviceTreeHeader::default(),
+        }
+    }
 
-        // // version 2 fields
-        // 28  boot_cpuid_phys: u32,
+    /// Load a device tree from a blob.
+    ///
+    /// The same as [`load`](DeviceTree::load), and the named form of the
+    /// `TryFrom<&[u8]>` conversion.
+    pub fn from_bytes(buffer: &[u8]) -> Result<DeviceTree> {
+        DeviceTree::try_from(buffer)
+    }
 
-        // // version 3 fields
-        // 32  size_dt_strings: u32,
+    pub fn load(buffer: &[u8]) -> Result<DeviceTree> {
+        Ok(BorrowedTree::load(buffer)?.to_tree())
+    }
 
-        // // version 17 fields
-        // 36  size_dt_struct: u32,
+    /// Memory regions reserved through the memory reservation block.
+    pub fn memory_reservations(&self) -> &[MemoryReservation] {
+        &self.reserved
+    }
 
-        if buffer.read_be_u32(0)? != MAGIC_NUMBER {
-            return Err(Error::InvalidMagicNumber);
-        }
+    /// The root node of the tree.
+    pub fn root(&self) -> &Node {
+        &self.root
+    }
 
-        // check total size
-        if buffer.read_be_u32(4)? as usize != buffer.len() {
-            return Err(Error::SizeMismatch);
-        }
+    /// Mutable access to the root node of the tree.
+    pub fn root_mut(&mut self) -> &mut Node {
+        &mut self.root
+    }
 
-        // check version
-        let version = buffer.read_be_u32(20)?;
-        if version != SUPPORTED_VERSION {
-            return Err(Error::VersionNotSupported);
-        }
+    /// The `model` of the root node, naming the board, e.g.
+    /// `Raspberry Pi 2 Model B`.
+    pub fn model(&self) -> Option<&str> {
+        self.root.property_str("model")
+    }
 
-        let off_dt_struct = buffer.read_be_u32(8)? as usize;
-        let off_dt_strings = buffer.read_be_u32(12)? as usize;
-        let off_mem_rsvmap = buffer.read_be_u32(16)? as usize;
-        let boot_cpuid_phys = buffer.read_be_u32(28)?;
+    /// The `serial-number` of the root node, if the board has one.
+    pub fn serial_number(&self) -> Option<&str> {
+        self.root.property_str("serial-number")
+    }
 
-        // load reserved memory list
-        let mut reserved = Vec::new();
-        let mut pos = off_mem_rsvmap;
+    /// The first, most specific, entry of the `compatible` list of the root
+    /// node, e.g. `brcm,bcm2709`. Linux logs it as the machine model when
+    /// there is no `model`.
+    pub fn linux_machine_compatible(&self) -> Option<&str> {
+        self.root.compatible().next()
+    }
 
-        loop {
-            let offset = buffer.read_be_u64(pos)?;
-            pos += 8;
-            let size = buffer.read_be_u64(pos)?;
-            pos += 8;
+    /// Header of the blob the tree was loaded from.
+    ///
+    /// Trees that were not loaded from a blob report a default header.
+    pub fn header(&self) -> &DeviceTreeHeader {
+        &self.header
+    }
 
-            reserved.push((offset, size));
+    /// Version, as indicated by the version header.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
 
-            if size == 0 {
-                break;
-            }
-        }
+    /// The number of the CPU the system boots from.
+    pub fn boot_cpuid_phys(&self) -> u32 {
+        self.boot_cpuid_phys
+    }
 
-        let (_, root) = Node::load(buffer, off_dt_struct, off_dt_strings)?;
+    /// Total size of the blob the tree was loaded from, `0` for trees that
+    /// were not loaded from a blob.
+    pub fn totalsize(&self) -> u32 {
+        self.header.totalsize
+    }
 
-        Ok(DeviceTree {
-            version,
-            boot_cpuid_phys,
-            reserved,
-            root,
-        })
+    /// Offset of the structure block in the blob the tree was loaded from.
+    pub fn off_dt_struct(&self) -> u32 {
+        self.header.off_dt_struct
+    }
+
+    /// Offset of the strings block in the blob the tree was loaded from.
+    pub fn off_dt_strings(&self) -> u32 {
+        self.header.off_dt_strings
     }
 
     pub fn find<'a>(&'a self, path: &str) -> Option<&'a Node> {
@@ -171,6 +285,423 @@ impl DeviceTree {
         self.root.find(&path[1..])
     }
 
+    /// Find a node by its absolute path, e.g. `/soc/uart@10000000`, or by a
+    /// path starting with an alias, e.g. `serial0` or `soc/uart@10000000`.
+    ///
+    /// `/` is the root node and trailing slashes are ignored. Anything from
+    /// the first `:` on is ignored, so `stdout-path` values such as
+    /// `serial0:115200n8` can be looked up directly. See [`Node::find`] for
+    /// how path components are matched.
+    pub fn find_node<'a>(&'a self, path: &str) -> Option<&'a Node> {
+        let (base, rest) = self.resolve_node_path(path)?;
+        self.find(base)?.find(rest)
+    }
+
+    /// Split `path`, as taken by [`find_node`](DeviceTree::find_node), into
+    /// an absolute path and a path relative to it, resolving any alias.
+    fn resolve_node_path<'a, 'p>(
+        &'a self,
+        path: &'p str,
+    ) -> Option<(&'a str, &'p str)> {
+        let path = path.split(':').next().unwrap_or_default();
+        if let Some(rest) = path.strip_prefix('/') {
+            return Some(("/", rest));
+        }
+
+        let (alias, rest) = path.split_once('/').unwrap_or((path, ""));
+        Some((self.resolve_alias(alias)?, rest))
+    }
+
+    /// Iterate over all nodes in depth-first (document) order.
+    ///
+    /// The same as [`iter_dfs`](DeviceTree::iter_dfs).
+    pub fn iter(&self) -> Dfs<'_> {
+        self.iter_dfs()
+    }
+
+    /// Iterate over all nodes in depth-first (document) order.
+    pub fn iter_dfs(&self) -> Dfs<'_> {
+        Dfs::new(&self.root)
+    }
+
+    /// Iterate over all nodes in breadth-first order.
+    pub fn iter_bfs(&self) -> Bfs<'_> {
+        Bfs::new(&self.root)
+    }
+
+    /// Iterate over all nodes in depth-first order, along with their depth.
+    ///
+    /// The root node has depth `0`.
+    pub fn iter_dfs_with_depth(&self) -> DfsWithDepth<'_> {
+        DfsWithDepth::new(&self.root)
+    }
+
+    /// Iterate over all nodes in depth-first order, along with their
+    /// absolute path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use flat_device_tree::DeviceTreeBuilder;
+    /// # let mut builder = DeviceTreeBuilder::new();
+    /// # builder.root("").child("soc").child("uart@1000");
+    /// # let dt = builder.build().unwrap();
+    /// let paths: Vec<_> = dt.iter_with_paths().map(|(path, _)| path).collect();
+    ///
+    /// assert_eq!(paths, ["/", "/soc", "/soc/uart@1000"]);
+    /// ```
+    pub fn iter_with_paths(&self) -> DfsWithPaths<'_> {
+        DfsWithPaths::new(&self.root)
+    }
+
+    /// Iterate over every property in the tree, along with the absolute
+    /// path of the node it belongs to.
+    ///
+    /// Nodes are visited in depth-first (document) order. The properties of
+    /// a node come in the order they appear in the node, before any
+    /// property of its children.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// // where is the node with phandle 0x2c referred to?
+    /// for (path, prop) in dt.properties_with_paths() {
+    ///     if prop.name() != "phandle" && prop.cells().any(|c| c == 0x2c) {
+    ///         println!("{}:{}", path, prop.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn properties_with_paths(&self) -> PropertiesWithPaths<'_> {
+        PropertiesWithPaths::new(&self.root)
+    }
+
+    /// Every property in the tree as a flat list of keys and raw values,
+    /// much like the files under `/proc/device-tree`.
+    ///
+    /// Each key is the absolute path of the node, a `:` and the name of the
+    /// property, e.g. `/soc/uart@1000:status` or `/:model` for the root
+    /// node. The separator cannot appear in node names, so keys are unique
+    /// even if a node has a property and a child of the same name. Entries
+    /// are in the order of
+    /// [`properties_with_paths`](DeviceTree::properties_with_paths).
+    pub fn flatten(&self) -> Vec<(String, Vec<u8>)> {
+        self.properties_with_paths()
+            .map(|(mut key, prop)| {
+                key.push(':');
+                key.push_str(&prop.name);
+                (key, prop.data.clone())
+            })
+            .collect()
+    }
+
+    /// Iterate over the nodes, in depth-first order, whose `compatible`
+    /// list contains `compat`.
+    ///
+    /// Entries are matched exactly, not as substrings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// let uart = dt.find_compatible("arm,pl011").next().unwrap();
+    /// assert_eq!(uart.name(), "uart@7e201000");
+    /// ```
+    pub fn find_compatible<'a: 'c, 'c>(
+        &'a self,
+        compat: &'c str,
+    ) -> impl Iterator<Item = &'a Node> + 'c {
+        self.iter_dfs()
+            .filter(move |node| node.is_compatible(compat))
+    }
+
+    /// Iterate over the nodes, in depth-first order, whose `compatible`
+    /// list contains any of `compats`, such as all models a driver
+    /// supports.
+    pub fn find_compatible_any<'a: 'c, 'c>(
+        &'a self,
+        compats: &'c [&'c str],
+    ) -> impl Iterator<Item = &'a Node> + 'c {
+        self.iter_dfs().filter(move |node| {
+            compats.iter().any(|compat| node.is_compatible(compat))
+        })
+    }
+
+    /// Iterate over the nodes, in depth-first order, that have a property
+    /// called `name`, such as every `interrupt-controller`.
+    ///
+    /// Chain [`Node::is_enabled`] as a filter to skip disabled nodes.
+    pub fn find_with_property<'a: 'c, 'c>(
+        &'a self,
+        name: &'c str,
+    ) -> impl Iterator<Item = &'a Node> + 'c {
+        self.iter_dfs().filter(move |node| node.has_property(name))
+    }
+
+    /// Iterate over the nodes, in depth-first order, that have a property
+    /// called `name` whose raw value is exactly `value`.
+    ///
+    /// String values must include their NUL terminator.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// let mut memory = dt.find_with_property_value("device_type", b"memory\0");
+    /// assert_eq!(memory.next().unwrap().name(), "memory");
+    /// ```
+    pub fn find_with_property_value<'a: 'c, 'c>(
+        &'a self,
+        name: &'c str,
+        value: &'c [u8],
+    ) -> impl Iterator<Item = &'a Node> + 'c {
+        self.iter_dfs().filter(move |node| {
+            node.property(name).is_some_and(|prop| prop.data() == value)
+        })
+    }
+
+    /// Find all nodes, in depth-first order, that have a property called
+    /// `name`.
+    pub fn find_nodes_with_property<'a>(&'a self, name: &str) -> Vec<&'a Node> {
+        self.find_with_property(name).collect()
+    }
+
+    /// Find all nodes, in depth-first order, that have a property called
+    /// `name` whose raw value is exactly `value`, such as every node with a
+    /// `device_type` of `b"cpu\0"`.
+    pub fn find_nodes_by_property<'a>(
+        &'a self,
+        name: &str,
+        value: &[u8],
+    ) -> Vec<&'a Node> {
+        self.find_with_property_value(name, value).collect()
+    }
+
+    /// Find all nodes, in depth-first order, whose `compatible` list contains
+    /// `compat`.
+    pub fn find_all_compatible<'a>(&'a self, compat: &str) -> Vec<&'a Node> {
+        self.find_compatible(compat).collect()
+    }
+
+    /// Like [`find_compatible`](DeviceTree::find_compatible), but skipping
+    /// nodes that are not [enabled](Node::is_enabled).
+    pub fn find_enabled_compatible<'a: 'c, 'c>(
+        &'a self,
+        compat: &'c str,
+    ) -> impl Iterator<Item = &'a Node> + 'c {
+        self.find_compatible(compat)
+            .filter(|node| node.is_enabled())
+    }
+
+    /// Like [`find_all_compatible`](DeviceTree::find_all_compatible), but
+    /// skipping nodes that are not [enabled](Node::is_enabled).
+    pub fn find_all_enabled_compatible<'a>(
+        &'a self,
+        compat: &str,
+    ) -> Vec<&'a Node> {
+        self.find_enabled_compatible(compat).collect()
+    }
+
+    /// Mutable variant of [`find_node`](DeviceTree::find_node), accepting
+    /// the same paths.
+    pub fn find_node_mut<'a>(&'a mut self, path: &str) -> Option<&'a mut Node> {
+        let (base, rest) = self.resolve_node_path(path)?;
+        // the alias path borrows the tree, which is about to be changed
+        let base = base.to_owned();
+        self.find_mut(&base)?.find_mut(rest)
+    }
+
+    /// Mutable variant of [`find`](DeviceTree::find), which only accepts
+    /// absolute paths.
+    pub(crate) fn find_mut<'a>(
+        &'a mut self,
+        path: &str,
+    ) -> Option<&'a mut Node> {
+        self.root.find_mut(path.strip_prefix('/')?)
+    }
+
+    /// A copy of the node at the absolute `path` and everything below it,
+    /// as a tree of its own.
+    ///
+    /// The node becomes the root of the new tree, so its name is cleared.
+    /// The new tree has no memory reservations and a default header, like
+    /// one created with [`new`](DeviceTree::new).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use flat_device_tree::{DeviceTree, DeviceTreeBuilder};
+    /// # let mut builder = DeviceTreeBuilder::new();
+    /// # let mut root = builder.root("");
+    /// # root.child("cpus").child("cpu@0").property_u32("reg", 0);
+    /// # let dt = builder.build().unwrap();
+    /// let cpus = dt.subtree("/cpus").unwrap();
+    /// let dtb = cpus.store().unwrap();
+    ///
+    /// let loaded = DeviceTree::load(&dtb).unwrap();
+    /// let cpu = loaded.find("/cpu@0").unwrap();
+    /// assert_eq!(cpu.property_u32("reg"), Some(0));
+    /// ```
+    pub fn subtree(&self, path: &str) -> Option<DeviceTree> {
+        let mut root = self.find(path)?.clone();
+        root.name.clear();
+
+        Some(DeviceTree::new(root))
+    }
+
+    /// Remove the node at the absolute `path`, and everything below it.
+    ///
+    /// Returns whether there was such a node. The root node cannot be
+    /// removed.
+    pub fn prune(&mut self, path: &str) -> bool {
+        let Some((parent, name)) = split_path(path) else {
+            return false;
+        };
+        let Some(parent) = self.find_mut(parent) else {
+            return false;
+        };
+
+        match parent.find_segment(name) {
+            Some(idx) => {
+                parent.children.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Set the value of the named property of the node at the absolute
+    /// `node_path`, adding the property if it does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use flat_device_tree::DeviceTreeBuilder;
+    /// # let mut builder = DeviceTreeBuilder::new();
+    /// # builder.root("");
+    /// # let mut dt = builder.build().unwrap();
+    /// dt.add_node("/", "soc").unwrap();
+    /// dt.add_node("/soc", "uart@1000").unwrap();
+    /// dt.set_property("/soc/uart@1000", "status", b"okay\0".to_vec())
+    ///     .unwrap();
+    ///
+    /// let uart = dt.find("/soc/uart@1000").unwrap();
+    /// assert_eq!(uart.property_str("status"), Some("okay"));
+    /// ```
+    pub fn set_property(
+        &mut self,
+        node_path: &str,
+        name: &str,
+        data: Vec<u8>,
+    ) -> TreeResult<()> {
+        let node = self.find_mut(node_path).ok_or(TreeError::NodeNotFound)?;
+
+        Ok(node.set_property(name, data)?)
+    }
+
+    /// Remove the named property of the node at the absolute `node_path`.
+    /// Returns whether the property existed.
+    pub fn delete_property(
+        &mut self,
+        node_path: &str,
+        name: &str,
+    ) -> TreeResult<bool> {
+        let node = self.find_mut(node_path).ok_or(TreeError::NodeNotFound)?;
+
+        Ok(node.remove_property(name))
+    }
+
+    /// Add an empty node called `name` as the last child of the node at the
+    /// absolute `parent_path`.
+    ///
+    /// See [`graft`](DeviceTree::graft) to add a node with contents.
+    pub fn add_node(
+        &mut self,
+        parent_path: &str,
+        name: &str,
+    ) -> TreeResult<()> {
+        let node = Node::new(name);
+
+        Ok(self.graft(parent_path, node)?)
+    }
+
+    /// Add `node`, and everything below it, as the last child of the node
+    /// at the absolute path `parent_path`.
+    ///
+    /// Fails without changing the tree if there is no such parent, if it
+    /// already has a child of the same name or if a name in `node` is
+    /// invalid.
+    pub fn graft(&mut self, parent_path: &str, node: Node) -> GraftResult<()> {
+        let parent = self
+            .find_mut(parent_path)
+            .ok_or(GraftError::ParentNotFound)?;
+        if parent.find_child(&node.name).is_some() {
+            return Err(GraftError::DuplicateChildName);
+        }
+
+        Ok(parent.add_child(node)?)
+    }
+
+    /// Change the name of the node at the absolute `path` to `new_name`,
+    /// keeping its unit address.
+    ///
+    /// `new_name` must not have a unit address of its own, so renaming
+    /// `/soc/serial@1000` to `uart` yields `/soc/uart@1000`. Fails without
+    /// changing the tree if there is no such node, if it is the root node,
+    /// if `new_name` is invalid or if a sibling already has the new name.
+    pub fn rename_node(
+        &mut self,
+        path: &str,
+        new_name: &str,
+    ) -> RenameResult<()> {
+        let (parent, name) =
+            split_path(path).ok_or_else(|| match self.find(path) {
+                Some(_) => RenameError::RootNode,
+                None => RenameError::NodeNotFound,
+            })?;
+        let parent = self.find_mut(parent).ok_or(RenameError::NodeNotFound)?;
+        let idx = parent.find_segment(name).ok_or(RenameError::NodeNotFound)?;
+
+        let mut renamed = String::from(new_name);
+        if let Some(unit) = parent.children[idx].unit_address() {
+            renamed.push('@');
+            renamed.push_str(unit);
+        }
+        if new_name.contains('@') || !is_valid_node_name(&renamed) {
+            return Err(RenameError::InvalidNodeName);
+        }
+        if parent
+            .children
+            .iter()
+            .enumerate()
+            .any(|(i, child)| i != idx && child.name == renamed)
+        {
+            return Err(RenameError::DuplicateChildName);
+        }
+
+        parent.children[idx].name = renamed;
+        Ok(())
+    }
+
+    /// Find all nodes matching a path pattern such as `/soc/*/ethernet@*`.
+    ///
+    /// See the [`glob`] module for the pattern syntax.
+    pub fn find_nodes_glob<'a, 'p>(&'a self, pattern: &'p str) -> Glob<'a, 'p> {
+        Glob::new(&self.root, pattern)
+    }
+
     pub fn store(&self) -> Result<Vec<u8>> {
         let mut dtb = Vec::new();
         let mut strings = StringTable::new();
@@ -207,13 +738,17 @@ impl DeviceTree {
         dtb.pad(8)?;
         let len = dtb.len();
         dtb.write_be_u32(off_mem_rsvmap, len as u32)?;
-        for reservation in self.reserved.iter() {
+        let terminator = MemoryReservation {
+            address: 0,
+            size: 0,
+        };
+        for reservation in self.reserved.iter().chain([&terminator]) {
             // address
             let len = dtb.len();
-            dtb.write_be_u64(len, reservation.0)?;
+            dtb.write_be_u64(len, reservation.address)?;
             // size
             let len = dtb.len();
-            dtb.write_be_u64(len, reservation.1)?;
+            dtb.write_be_u64(len, reservation.size)?;
         }
 
         // Structure Block
@@ -243,96 +778,352 @@ impl DeviceTree {
     }
 }
 
+impl TryFrom<&[u8]> for DeviceTree {
+    type Error = Error;
+
+    fn try_from(buffer: &[u8]) -> Result<DeviceTree> {
+        DeviceTree::load(buffer)
+    }
+}
+
+impl TryFrom<Vec<u8>> for DeviceTree {
+    type Error = Error;
+
+    /// Load a device tree from an owned blob. The tree does not borrow from
+    /// the blob, which is dropped afterwards.
+    fn try_from(buffer: Vec<u8>) -> Result<DeviceTree> {
+        DeviceTree::load(&buffer)
+    }
+}
+
+/// Split a node name into its base name and unit address.
+fn split_name(name: &str) -> (&str, Option<&str>) {
+    match name.split_once('@') {
+        Some((base, address)) => (base, Some(address)),
+        None => (name, None),
+    }
+}
+
+/// The path of the child `name` of the node at `parent`.
+fn child_path(parent: &str, name: &str) -> String {
+    let mut path = String::from(parent);
+    if !path.ends_with('/') {
+        path.push('/');
+    }
+    path.push_str(name);
+    path
+}
+
+impl<'a> IntoIterator for &'a Node {
+    type Item = &'a Node;
+    type IntoIter = slice::Iter<'a, Node>;
+
+    /// Iterate over the children of the node.
+    fn into_iter(self) -> slice::Iter<'a, Node> {
+        self.children.iter()
+    }
+}
+
+/// Split an absolute path into the path of the parent and the name of the
+/// node. Returns `None` for the root node.
+fn split_path(path: &str) -> Option<(&str, &str)> {
+    let path = path.trim_end_matches('/');
+    let (parent, name) = path.rsplit_once('/')?;
+    if !path.starts_with('/') || name.is_empty() {
+        return None;
+    }
+
+    match parent {
+        "" => Some(("/", name)),
+        _ => Some((parent, name)),
+    }
+}
+
+/// Skip over any `OF_DT_NOP` tokens starting at `pos`.
+fn skip_nops(buffer: &[u8], mut pos: usize) -> Result<usize> {
+    while buffer.read_be_u32(pos)? == OF_DT_NOP {
+        pos += 4;
+    }
+
+    Ok(pos)
+}
+
 impl Node {
-    fn load(
-        buffer: &[u8],
-        start: usize,
-        off_dt_strings: usize,
-    ) -> Result<(usize, Node)> {
-        // check for DT_BEGIN_NODE
-        if buffer.read_be_u32(start)? != OF_DT_BEGIN_NODE {
-            return Err(Error::ParseError(start));
+    /// Create a node without properties or children.
+    ///
+    /// Properties and children are added with
+    /// [`set_property`](Node::set_property) and
+    /// [`add_child`](Node::add_child), which check their names.
+    pub fn new(name: &str) -> Node {
+        Node {
+            name: name.to_owned(),
+            props: Vec::new(),
+            children: Vec::new(),
+            struct_offset: None,
         }
+    }
 
-        let raw_name = buffer.read_bstring0(start + 4)?;
+    /// The properties of this node, in the order they appear in the blob.
+    pub fn properties(&self) -> &[Property] {
+        &self.props
+    }
+
+    /// Mutable access to the properties of this node, e.g. to change their
+    /// values in place.
+    pub fn properties_mut(&mut self) -> &mut [Property] {
+        &mut self.props
+    }
 
-        // read all the props
-        let mut pos = align(start + 4 + raw_name.len() + 1, 4);
+    /// Offset of the `FDT_BEGIN_NODE` token of this node in the blob the
+    /// tree was loaded from.
+    ///
+    /// Returns `None` for nodes that were not loaded from a blob. The
+    /// offset is not updated when the tree is changed or stored again.
+    pub fn struct_offset(&self) -> Option<usize> {
+        self.struct_offset
+    }
 
-        let mut props = Vec::new();
+    /// The children of this node, in the order they appear in the blob.
+    ///
+    /// `&Node` also iterates over its children, so this is the same as
+    /// `node.into_iter()`.
+    pub fn children(&self) -> &[Node] {
+        &self.children
+    }
 
-        while buffer.read_be_u32(pos)? == OF_DT_PROP {
-            let val_size = buffer.read_be_u32(pos + 4)? as usize;
-            let name_offset = buffer.read_be_u32(pos + 8)? as usize;
+    /// Mutable access to the children of this node.
+    pub fn children_mut(&mut self) -> &mut [Node] {
+        &mut self.children
+    }
 
-            // get value slice
-            let val_start = pos + 12;
-            let val_end = val_start + val_size;
-            let val = buffer.subslice(val_start, val_end)?;
+    /// Iterate over this node and its descendants in depth-first
+    /// (document) order.
+    pub fn iter(&self) -> Dfs<'_> {
+        Dfs::new(self)
+    }
 
-            // lookup name in strings table
-            let prop_name = buffer.read_bstring0(off_dt_strings + name_offset)?;
+    /// Iterate over this node and its descendants in breadth-first order,
+    /// level by level, down to `max_depth` levels below this node if given.
+    ///
+    /// Yields the same items as [`iter`](Node::iter), so the same adapters
+    /// work with either order.
+    pub fn iter_breadth_first(&self, max_depth: Option<usize>) -> Bfs<'_> {
+        Bfs::with_max_depth(self, max_depth)
+    }
 
-            props.push((str::from_utf8(prop_name)?.to_owned(), val.to_owned()));
+    pub fn find<'a>(&'a self, path: &str) -> Option<&'a Node> {
+        path.split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(self, |node, name| {
+                node.find_segment(name).map(|idx| &node.children[idx])
+            })
+    }
 
-            pos = align(val_end, 4);
+    pub fn find_mut<'a>(&'a mut self, path: &str) -> Option<&'a mut Node> {
+        let mut node = self;
+        for name in path.split('/').filter(|name| !name.is_empty()) {
+            let idx = node.find_segment(name)?;
+            node = &mut node.children[idx];
         }
 
-        // finally, parse children
-        let mut children = Vec::new();
+        Some(node)
+    }
 
-        while buffer.read_be_u32(pos)? == OF_DT_BEGIN_NODE {
-            let (new_pos, child_node) = Node::load(buffer, pos, off_dt_strings)?;
-            pos = new_pos;
+    /// The name of the node, as it appears in the node path, e.g.
+    /// `uart@ff000000`. The root node has an empty name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 
-            children.push(child_node);
+    /// Change the name of the node.
+    ///
+    /// Fails if `name` is not of the form `node-name@unit-address`. Siblings
+    /// are out of reach, so this does not check that they have different
+    /// names; [`DeviceTree::rename_node`] does.
+    pub fn set_name(&mut self, name: &str) -> NodeResult<()> {
+        if !is_valid_node_name(name) {
+            return Err(NodeError::InvalidNodeName);
         }
 
-        if buffer.read_be_u32(pos)? != OF_DT_END_NODE {
-            return Err(Error::ParseError(pos));
-        }
+        name.clone_into(&mut self.name);
+        Ok(())
+    }
 
-        pos += 4;
+    /// The name of the node without its unit address, e.g. `uart` for
+    /// `uart@ff000000`.
+    pub fn base_name(&self) -> &str {
+        split_name(&self.name).0
+    }
 
-        Ok((
-            pos,
-            Node {
-                name: str::from_utf8(raw_name)?.to_owned(),
-                props,
-                children,
-            },
-        ))
+    /// The unit address part of the name, e.g. `ff000000` for
+    /// `uart@ff000000`, or `None` if the name has none.
+    pub fn unit_address(&self) -> Option<&str> {
+        split_name(&self.name).1
     }
 
-    pub fn find<'a>(&'a self, path: &str) -> Option<&'a Node> {
-        if path.is_empty() {
-            return Some(self);
+    /// Find the child with the given name, including its unit address.
+    pub fn find_child<'a>(&'a self, name: &str) -> Option<&'a Node> {
+        self.children.iter().find(|n| n.name == name)
+    }
+
+    /// Find the first child with the given name, ignoring unit addresses.
+    ///
+    /// `find_child_by_base_name("memory")` matches both `memory` and
+    /// `memory@80000000`.
+    pub fn find_child_by_base_name<'a>(
+        &'a self,
+        name: &str,
+    ) -> Option<&'a Node> {
+        self.children.iter().find(|n| split_name(&n.name).0 == name)
+    }
+
+    /// Iterate over the children with the given name, ignoring unit
+    /// addresses, in document order.
+    pub fn children_by_name<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a Node> + 'a {
+        self.children
+            .iter()
+            .filter(move |n| split_name(&n.name).0 == name)
+    }
+
+    /// The number of children of this node.
+    pub fn child_count(&self) -> usize {
+        self.children.len()
+    }
+
+    /// The child at the given position.
+    pub fn child(&self, index: usize) -> Option<&Node> {
+        self.children.get(index)
+    }
+
+    /// Find the index of the child matching a single path component.
+    ///
+    /// Names are matched exactly, including the unit address. A name without
+    /// a unit address also matches a child that has one, as long as there is
+    /// only one child with that name.
+    fn find_segment(&self, name: &str) -> Option<usize> {
+        if let Some(idx) = self.children.iter().position(|n| n.name == name) {
+            return Some(idx);
         }
 
-        match path.find('/') {
-            Some(idx) => {
-                // find should return the proper index, so we're safe to
-                // use indexing here
-                let (l, r) = path.split_at(idx);
+        if name.contains('@') {
+            return None;
+        }
 
-                // we know that the first char of slashed is a '/'
-                let subpath = &r[1..];
+        let mut matches = self
+            .children
+            .iter()
+            .enumerate()
+            .filter(|(_, n)| split_name(&n.name).0 == name);
 
-                for child in self.children.iter() {
-                    if child.name == l {
-                        return child.find(subpath);
-                    }
-                }
+        match (matches.next(), matches.next()) {
+            (Some((idx, _)), None) => Some(idx),
+            _ => None,
+        }
+    }
 
-                // no matching child found
-                None
-            }
-            None => self.children.iter().find(|n| n.name == path),
+    /// Iterate over the entries of the `compatible` list, most specific
+    /// first. Empty entries are skipped, and there are no entries if the
+    /// property is missing.
+    pub fn compatible(&self) -> impl Iterator<Item = &str> {
+        self.property("compatible")
+            .into_iter()
+            .flat_map(|prop| prop.strings())
+            .filter(|entry| !entry.is_empty())
+    }
+
+    /// Whether one of the entries of the `compatible` list equals `compat`.
+    pub fn is_compatible(&self, compat: &str) -> bool {
+        self.compatible().any(|entry| entry == compat)
+    }
+
+    /// Look up the property with the given name.
+    pub fn property<'a>(&'a self, name: &str) -> Option<&'a Property> {
+        self.props.iter().find(|p| p.name == name)
+    }
+
+    /// Mutable variant of [`property`](Node::property).
+    pub fn property_mut<'a>(
+        &'a mut self,
+        name: &str,
+    ) -> Option<&'a mut Property> {
+        self.props.iter_mut().find(|p| p.name == name)
+    }
+
+    /// The value of the named property as a single big-endian `u32`.
+    ///
+    /// Unlike [`prop_u32`](Node::prop_u32), this returns `None` if the value
+    /// is not exactly 4 bytes long.
+    pub fn property_u32(&self, name: &str) -> Option<u32> {
+        self.property(name)?.as_u32()
+    }
+
+    /// The value of the named property as a NUL-terminated string.
+    ///
+    /// See [`Property::as_str`].
+    pub fn property_str<'a>(&'a self, name: &str) -> Option<&'a str> {
+        self.property(name)?.as_str()
+    }
+
+    /// Whether the node has a property with the given name.
+    pub fn has_property(&self, name: &str) -> bool {
+        self.property(name).is_some()
+    }
+
+    /// Whether the node has an empty property with the given name.
+    ///
+    /// Flags such as `dma-coherent` are expressed this way. Use
+    /// [`has_property`](Node::has_property) to check for a property
+    /// regardless of its value.
+    pub fn has_flag(&self, name: &str) -> bool {
+        self.property(name).is_some_and(Property::is_empty)
+    }
+
+    /// Set the value of the named property, adding it if it does not exist.
+    ///
+    /// Fails if `name` is empty or not printable ASCII.
+    pub fn set_property(
+        &mut self,
+        name: &str,
+        data: Vec<u8>,
+    ) -> NodeResult<()> {
+        if !is_valid_property_name(name) {
+            return Err(NodeError::InvalidPropertyName);
         }
+
+        match self.property_mut(name) {
+            Some(prop) => prop.data = data,
+            None => self.props.push(Property::new(name, data)),
+        }
+        Ok(())
+    }
+
+    /// Remove the named property. Returns whether it existed.
+    pub fn remove_property(&mut self, name: &str) -> bool {
+        let len = self.props.len();
+        self.props.retain(|p| p.name != name);
+        self.props.len() != len
+    }
+
+    /// Append a child node.
+    ///
+    /// Fails if the name of `child` or any name below it is invalid.
+    pub fn add_child(&mut self, child: Node) -> NodeResult<()> {
+        validate_node(&child)?;
+        self.children.push(child);
+        Ok(())
+    }
+
+    /// Remove the child with the given name and return it.
+    pub fn remove_child(&mut self, name: &str) -> Option<Node> {
+        let idx = self.children.iter().position(|n| n.name == name)?;
+        Some(self.children.remove(idx))
     }
 
     pub fn has_prop(&self, name: &str) -> bool {
-        self.prop_raw(name).is_some()
+        self.has_property(name)
     }
 
     pub fn prop_str<'a>(&'a self, name: &str) -> Result<&'a str> {
@@ -347,12 +1138,7 @@ impl Node {
     }
 
     pub fn prop_raw<'a>(&'a self, name: &str) -> Option<&'a Vec<u8>> {
-        for (key, val) in self.props.iter() {
-            if key == name {
-                return Some(val);
-            }
-        }
-        None
+        self.property(name).map(|p| &p.data)
     }
 
     pub fn prop_u64(&self, name: &str) -> Result<u64> {
@@ -385,15 +1171,15 @@ impl Node {
             // Write property value length
             structure.pad(4)?;
             let len = structure.len();
-            structure.write_be_u32(len, prop.1.len() as u32)?;
+            structure.write_be_u32(len, prop.data.len() as u32)?;
 
             // Write name offset
             structure.pad(4)?;
             let len = structure.len();
-            structure.write_be_u32(len, strings.add_string(&prop.0))?;
+            structure.write_be_u32(len, strings.add_string(&prop.name))?;
 
             // Store the property value
-            structure.extend_from_slice(&prop.1);
+            structure.extend_from_slice(&prop.data);
         }
 
         // Recurse on children
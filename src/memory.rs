@@ -0,0 +1,155 @@
+use alloc::vec::Vec;
+
+use crate::property::read_cells;
+use crate::{DeviceTree, Node};
+
+/// A range of physical RAM described by a memory node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemoryRegion {
+    /// Physical address of the first byte of the region.
+    pub base: u64,
+
+    /// Size of the region in bytes.
+    pub size: u64,
+}
+
+impl MemoryRegion {
+    /// The address one past the last byte of the region, saturating at
+    /// `u64::MAX`.
+    pub fn end(&self) -> u64 {
+        self.base.saturating_add(self.size)
+    }
+
+    /// Whether `addr` lies within the region.
+    pub fn contains(&self, addr: u64) -> bool {
+        addr.checked_sub(self.base)
+            .is_some_and(|offset| offset < self.size)
+    }
+}
+
+/// A child of `/reserved-memory`, describing memory set aside for a
+/// particular use such as a firmware carveout or a CMA pool.
+///
+/// Created by [`DeviceTree::reserved_memory`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReservedMemory<'a> {
+    /// The name of the node, such as `linux,cma`.
+    pub name: &'a str,
+
+    /// The regions of a static reservation, from `reg`. Empty for dynamic
+    /// reservations.
+    pub regions: Vec<MemoryRegion>,
+
+    /// The size of a dynamic reservation, from `size`.
+    pub size: Option<u64>,
+
+    /// The required alignment of a dynamic reservation, from `alignment`.
+    pub alignment: Option<u64>,
+
+    /// The ranges a dynamic reservation may be allocated from, from
+    /// `alloc-ranges`. Empty if it may be allocated anywhere.
+    pub alloc_ranges: Vec<MemoryRegion>,
+
+    /// Whether the memory must not be mapped by the operating system, from
+    /// `no-map`.
+    pub no_map: bool,
+
+    /// Whether the operating system may use the memory while the device
+    /// driver does not, from `reusable`.
+    pub reusable: bool,
+}
+
+impl<'a> ReservedMemory<'a> {
+    /// Decode `node`, a child of the `/reserved-memory` node `parent`.
+    fn new(parent: &Node, node: &'a Node) -> ReservedMemory<'a> {
+        let regions = |name| {
+            node.decode_reg(name, parent)
+                .into_iter()
+                .flatten()
+                .map(|entry| MemoryRegion {
+                    base: entry.address,
+                    size: entry.size,
+                })
+                .collect()
+        };
+        // sizes are given in #size-cells of the parent
+        let size = |name| {
+            let data = node.property(name)?.data();
+            if data.len() != parent.size_cells()? as usize * 4 {
+                return None;
+            }
+            read_cells(data)
+        };
+
+        ReservedMemory {
+            name: &node.name,
+            regions: regions("reg"),
+            size: size("size"),
+            alignment: size("alignment"),
+            alloc_ranges: regions("alloc-ranges"),
+            no_map: node.property("no-map").is_some(),
+            reusable: node.property("reusable").is_some(),
+        }
+    }
+}
+
+impl DeviceTree {
+    /// The RAM regions described by all enabled nodes with a `device_type`
+    /// of `memory`, in document order.
+    ///
+    /// The `reg` properties are decoded using the cell counts of the root
+    /// node, and every entry of a node is a separate region. Memory nodes
+    /// without a valid `reg` property are skipped, as are nodes whose
+    /// `status` is not `okay`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use flat_device_tree::DeviceTreeBuilder;
+    /// # let mut builder = DeviceTreeBuilder::new();
+    /// # let mut root = builder.root("");
+    /// # root.property_u32("#address-cells", 1).property_u32("#size-cells", 1);
+    /// # root.child("memory@0")
+    /// #     .property_str("device_type", "memory")
+    /// #     .property("reg", &[0, 0, 0, 0, 0x40, 0, 0, 0]);
+    /// # let dt = builder.build().unwrap();
+    /// let ram: u64 = dt.memory().map(|region| region.size).sum();
+    /// assert_eq!(ram, 0x4000_0000);
+    /// ```
+    pub fn memory(&self) -> impl Iterator<Item = MemoryRegion> + '_ {
+        self.iter_dfs()
+            .filter(|node| node.property_str("device_type") == Some("memory"))
+            .filter(|node| node.is_enabled())
+            .filter_map(|node| node.reg(&self.root))
+            .flatten()
+            .map(|entry| MemoryRegion {
+                base: entry.address,
+                size: entry.size,
+            })
+    }
+
+    /// The regions of [`memory`](DeviceTree::memory), collected.
+    pub fn memory_regions(&self) -> Vec<MemoryRegion> {
+        self.memory().collect()
+    }
+
+    /// The enabled children of the `/reserved-memory` node, in document
+    /// order.
+    ///
+    /// These are in addition to the [memory reservation
+    /// block](DeviceTree::memory_reservations) of the header. The `reg`,
+    /// `size`, `alignment` and `alloc-ranges` properties are decoded using
+    /// the cell counts of `/reserved-memory`, not those of the root node.
+    /// Malformed properties are left empty.
+    pub fn reserved_memory(&self) -> impl Iterator<Item = ReservedMemory<'_>> {
+        self.find("/reserved-memory")
+            .into_iter()
+            .flat_map(|parent| {
+                parent
+                    .children
+                    .iter()
+                    .filter(|node| node.is_enabled())
+                    .map(move |node| ReservedMemory::new(parent, node))
+            })
+    }
+}
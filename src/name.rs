@@ -0,0 +1,43 @@
+//! Validation of node and property names.
+
+use crate::{split_name, Node, NodeError, NodeResult};
+
+/// Whether `c` may appear in a node name or unit address.
+fn is_node_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b",._+-".contains(&c)
+}
+
+/// Whether `name` is a valid `node-name@unit-address` node name.
+///
+/// The unit address is optional, but must not be empty if the `@` is
+/// present.
+pub(crate) fn is_valid_node_name(name: &str) -> bool {
+    let (base, unit) = split_name(name);
+
+    let valid = |s: &str| !s.is_empty() && s.bytes().all(is_node_char);
+
+    valid(base) && unit.is_none_or(valid)
+}
+
+/// Whether `name` is a valid property name: non-empty printable ASCII
+/// without spaces.
+pub(crate) fn is_valid_property_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|c| c.is_ascii_graphic())
+}
+
+/// Check the name of `node` and everything below it.
+pub(crate) fn validate_node(node: &Node) -> NodeResult<()> {
+    if !is_valid_node_name(&node.name) {
+        return Err(NodeError::InvalidNodeName);
+    }
+    validate_contents(node)
+}
+
+/// Check the property names of `node` and everything below it, but not the
+/// name of `node` itself.
+pub(crate) fn validate_contents(node: &Node) -> NodeResult<()> {
+    if !node.props.iter().all(|p| is_valid_property_name(&p.name)) {
+        return Err(NodeError::InvalidPropertyName);
+    }
+    node.children.iter().try_for_each(validate_node)
+}
@@ -0,0 +1,240 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{DeviceTree, Node, OverlayError, OverlayResult, Property};
+
+impl Node {
+    /// Add `delta` to the phandles of this node and everything below it.
+    fn shift_phandles(&mut self, delta: u32) -> OverlayResult<()> {
+        for prop in self.props.iter_mut() {
+            if prop.name == "phandle" || prop.name == "linux,phandle" {
+                patch_cell(prop, 0, |phandle| shift_phandle(phandle, delta))?;
+            }
+        }
+
+        self.children
+            .iter_mut()
+            .try_for_each(|child| child.shift_phandles(delta))
+    }
+
+    /// Add `delta` to the phandles listed by `fixups`, the node of
+    /// `__local_fixups__` that mirrors this node.
+    fn apply_local_fixups(
+        &mut self,
+        fixups: &Node,
+        delta: u32,
+    ) -> OverlayResult<()> {
+        for fixup in fixups.props.iter() {
+            if !fixup.is_cell_aligned() {
+                return Err(OverlayError::InvalidFixup);
+            }
+
+            let prop = self
+                .property_mut(&fixup.name)
+                .ok_or(OverlayError::InvalidFixup)?;
+            for offset in fixup.cells() {
+                patch_cell(prop, offset, |phandle| {
+                    shift_phandle(phandle, delta)
+                })?;
+            }
+        }
+
+        for fixup in fixups.children.iter() {
+            let child = self
+                .children
+                .iter_mut()
+                .find(|child| child.name == fixup.name)
+                .ok_or(OverlayError::InvalidFixup)?;
+            child.apply_local_fixups(fixup, delta)?;
+        }
+
+        Ok(())
+    }
+
+    /// Merge the properties and children of `overlay` into this node.
+    /// Properties of `overlay` replace those of the same name, children are
+    /// merged into those of the same name.
+    fn merge(&mut self, overlay: &Node) {
+        for prop in overlay.props.iter() {
+            match self.property_mut(&prop.name) {
+                Some(existing) => existing.data.clone_from(&prop.data),
+                None => self.props.push(prop.clone()),
+            }
+        }
+
+        for child in overlay.children.iter() {
+            match self.children.iter_mut().find(|n| n.name == child.name) {
+                Some(existing) => existing.merge(child),
+                None => self.children.push(child.clone()),
+            }
+        }
+    }
+}
+
+fn shift_phandle(phandle: u32, delta: u32) -> OverlayResult<u32> {
+    // 0xffffffff marks a phandle that has yet to be fixed up
+    phandle
+        .checked_add(delta)
+        .filter(|&phandle| phandle != u32::MAX)
+        .ok_or(OverlayError::PhandleConflict)
+}
+
+/// Replace the cell at byte `offset` of the value of `prop`.
+fn patch_cell(
+    prop: &mut Property,
+    offset: u32,
+    patch: impl FnOnce(u32) -> OverlayResult<u32>,
+) -> OverlayResult<()> {
+    let offset = offset as usize;
+    let cell = offset
+        .checked_add(4)
+        .and_then(|end| prop.data.get_mut(offset..end))
+        .ok_or(OverlayError::InvalidFixup)?;
+
+    let value = u32::from_be_bytes([cell[0], cell[1], cell[2], cell[3]]);
+    cell.copy_from_slice(&patch(value)?.to_be_bytes());
+    Ok(())
+}
+
+impl DeviceTree {
+    /// Apply a device tree overlay, such as one loaded from a `.dtbo` file,
+    /// and return the resulting tree.
+    ///
+    /// The overlay must have been compiled with symbols (`dtc -@`):
+    ///
+    /// - The phandles of the overlay are moved past those of this tree, and
+    ///   references to them listed in `__local_fixups__` are updated.
+    /// - References to labels of this tree listed in `__fixups__` are
+    ///   resolved through the `__symbols__` of this tree.
+    /// - The contents of the `__overlay__` node of every fragment are
+    ///   merged into the node given by its `target` phandle or its
+    ///   `target-path`, which may start with an alias.
+    /// - The `__symbols__` of the overlay are added to those of this tree,
+    ///   with their paths pointing into the targets.
+    ///
+    /// This tree is left unchanged.
+    pub fn apply_overlay(&self, overlay: &DeviceTree) -> OverlayResult<Self> {
+        let mut tree = self.clone();
+        let mut overlay = overlay.root.clone();
+        let delta = self.iter_dfs().filter_map(Node::phandle_value).max();
+
+        overlay.shift_phandles(delta.unwrap_or(0))?;
+        if let Some(fixups) = overlay.remove_child("__local_fixups__") {
+            overlay.apply_local_fixups(&fixups, delta.unwrap_or(0))?;
+        }
+        if let Some(fixups) = overlay.remove_child("__fixups__") {
+            self.resolve_fixups(&mut overlay, &fixups)?;
+        }
+        let symbols = overlay.remove_child("__symbols__");
+
+        let mut targets = Vec::new();
+        for fragment in overlay.children.iter() {
+            let Some(contents) = fragment.find_child("__overlay__") else {
+                continue;
+            };
+
+            let path = tree.fragment_target(fragment)?;
+            // the path was just taken from the tree, so the node exists
+            if let Some(target) = tree.root.find_mut(&path) {
+                target.merge(contents);
+            }
+            targets.push((fragment.name.as_str(), path));
+        }
+
+        if let Some(symbols) = symbols {
+            tree.add_symbols(&symbols, &targets);
+        }
+
+        Ok(tree)
+    }
+
+    /// Write the phandles of the labels listed in `fixups` into `overlay`.
+    fn resolve_fixups(
+        &self,
+        overlay: &mut Node,
+        fixups: &Node,
+    ) -> OverlayResult<()> {
+        let symbols = self.find("/__symbols__");
+
+        for fixup in fixups.props.iter() {
+            let phandle = symbols
+                .and_then(|symbols| symbols.property_str(&fixup.name))
+                .and_then(|path| self.find(path))
+                .and_then(Node::phandle_value)
+                .ok_or(OverlayError::UnknownSymbol)?;
+
+            // each entry is "path:property:offset"
+            for entry in fixup.strings() {
+                let mut parts = entry.split(':');
+                let (Some(path), Some(name), Some(offset), None) =
+                    (parts.next(), parts.next(), parts.next(), parts.next())
+                else {
+                    return Err(OverlayError::InvalidFixup);
+                };
+                let offset =
+                    offset.parse().map_err(|_| OverlayError::InvalidFixup)?;
+
+                let prop = overlay
+                    .find_mut(path)
+                    .and_then(|node| node.property_mut(name))
+                    .ok_or(OverlayError::InvalidFixup)?;
+                patch_cell(prop, offset, |_| Ok(phandle))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The path of the node `fragment` targets.
+    fn fragment_target(&self, fragment: &Node) -> OverlayResult<String> {
+        let target = if let Some(phandle) = fragment.property_u32("target") {
+            self.find_by_phandle(phandle)
+        } else if let Some(path) = fragment.property_str("target-path") {
+            self.find_node(path)
+        } else {
+            return Err(OverlayError::MissingTarget);
+        };
+
+        target
+            .and_then(|node| self.node_path(node))
+            .ok_or(OverlayError::TargetNotFound)
+    }
+
+    /// Add `symbols` from an overlay, given the paths `targets` its
+    /// fragments were applied to.
+    fn add_symbols(&mut self, symbols: &Node, targets: &[(&str, String)]) {
+        let mut props = Vec::new();
+
+        for symbol in symbols.props.iter() {
+            // symbols point into "/fragment@N/__overlay__"
+            let Some((fragment, rest)) = symbol
+                .as_str()
+                .and_then(|path| path.strip_prefix('/')?.split_once('/'))
+            else {
+                continue;
+            };
+            let Some(rest) = rest.strip_prefix("__overlay__") else {
+                continue;
+            };
+            let Some((_, target)) =
+                targets.iter().find(|(name, _)| *name == fragment)
+            else {
+                continue;
+            };
+
+            let path = match (target.as_str(), rest) {
+                ("/", "") => String::from("/"),
+                ("/", rest) => String::from(rest),
+                (target, rest) => format!("{}{}", target, rest),
+            };
+            let mut data = path.into_bytes();
+            data.push(0);
+            props.push(Property::new(&symbol.name, data));
+        }
+
+        let mut symbols = Node::new("__symbols__");
+        symbols.props = props;
+        let mut root = Node::new("");
+        root.children.push(symbols);
+        self.root.merge(&root);
+    }
+}
@@ -0,0 +1,280 @@
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt, ptr};
+
+use crate::name::is_valid_node_name;
+use crate::{DeviceTree, Node, PathError, PathResult, Property};
+
+impl Node {
+    /// Record the nodes from below this node down to `target`. Returns
+    /// whether `target` was found.
+    fn path_to<'a>(&'a self, target: &Node, nodes: &mut Vec<&'a Node>) -> bool {
+        if ptr::eq(self, target) {
+            return true;
+        }
+
+        for child in self.children.iter() {
+            nodes.push(child);
+            if child.path_to(target, nodes) {
+                return true;
+            }
+            nodes.pop();
+        }
+
+        false
+    }
+}
+
+impl DeviceTree {
+    /// The absolute path of `node`, which must be a reference into this
+    /// tree, such as one returned by
+    /// [`find_compatible`](DeviceTree::find_compatible).
+    ///
+    /// Nodes are identified by address, not by contents, so this returns
+    /// `None` for an equal node that belongs to another tree. This walks the
+    /// tree, so it is best kept off hot paths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// let uart = dt.find_compatible("arm,pl011").next().unwrap();
+    /// assert_eq!(dt.node_path(uart).unwrap(), "/soc/uart@7e201000");
+    /// ```
+    pub fn node_path(&self, node: &Node) -> Option<String> {
+        let nodes = self.lineage(node)?;
+
+        let mut path = String::new();
+        for node in nodes[1..].iter() {
+            path.push('/');
+            path.push_str(&node.name);
+        }
+        if path.is_empty() {
+            path.push('/');
+        }
+
+        Some(path)
+    }
+
+    /// The nodes from the root down to `node`, which is identified by
+    /// address like in [`node_path`](DeviceTree::node_path).
+    pub(crate) fn lineage<'a>(&'a self, node: &Node) -> Option<Vec<&'a Node>> {
+        let mut nodes = vec![&self.root];
+        if !self.root.path_to(node, &mut nodes) {
+            return None;
+        }
+
+        Some(nodes)
+    }
+}
+
+/// A node along with the tree it belongs to, displayed as its absolute path.
+///
+/// # Examples
+///
+/// ```rust
+/// use flat_device_tree::{DeviceTree, NodeRef};
+///
+/// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+/// let dt = DeviceTree::load(buf).unwrap();
+///
+/// let uart = dt.find("/soc/uart@7e201000").unwrap();
+/// assert_eq!(NodeRef::new(&dt, uart).to_string(), "/soc/uart@7e201000");
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct NodeRef<'a> {
+    tree: &'a DeviceTree,
+    node: &'a Node,
+}
+
+impl<'a> NodeRef<'a> {
+    /// Pair `node` with the tree it belongs to.
+    pub fn new(tree: &'a DeviceTree, node: &'a Node) -> NodeRef<'a> {
+        NodeRef { tree, node }
+    }
+
+    /// The tree the node belongs to.
+    pub fn tree(&self) -> &'a DeviceTree {
+        self.tree
+    }
+
+    /// The node.
+    pub fn node(&self) -> &'a Node {
+        self.node
+    }
+}
+
+/// Displays the absolute path of the node, or just its name if it is not
+/// part of the tree.
+impl fmt::Display for NodeRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.tree.node_path(self.node) {
+            Some(path) => f.write_str(&path),
+            None => f.write_str(&self.node.name),
+        }
+    }
+}
+
+/// An absolute node path, such as `/soc/uart@7e201000`, that is known to be
+/// well-formed.
+///
+/// # Examples
+///
+/// ```rust
+/// use flat_device_tree::NodePath;
+///
+/// let path = NodePath::parse("/soc").unwrap().push("uart@7e201000").unwrap();
+/// assert_eq!(path.to_string(), "/soc/uart@7e201000");
+/// assert_eq!(path.parent().unwrap().as_str(), "/soc");
+/// ```
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct NodePath(String);
+
+impl NodePath {
+    /// The path of the root node, `/`.
+    pub fn root() -> NodePath {
+        NodePath(String::from("/"))
+    }
+
+    /// Check that `s` is an absolute path made up of valid node names.
+    ///
+    /// `/` is the root node. Empty components, such as from a doubled or
+    /// trailing slash, are not allowed.
+    pub fn parse(s: &str) -> PathResult<NodePath> {
+        let rest = s.strip_prefix('/').ok_or(PathError::NotAbsolute)?;
+        if !rest.is_empty() {
+            rest.split('/').try_for_each(check_component)?;
+        }
+
+        Ok(NodePath(String::from(s)))
+    }
+
+    /// A path built from the names of the nodes of a tree, which are not
+    /// checked again.
+    pub(crate) fn from_tree(path: String) -> NodePath {
+        NodePath(path)
+    }
+
+    /// The path as a string, e.g. to pass to [`DeviceTree::find`].
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The names of the nodes along the path, below the root node.
+    pub fn components(&self) -> impl Iterator<Item = &str> {
+        self.0.split('/').filter(|name| !name.is_empty())
+    }
+
+    /// The path of the parent node, or `None` for the root node.
+    pub fn parent(&self) -> Option<NodePath> {
+        match self.0.rsplit_once('/')? {
+            (_, "") => None,
+            ("", _) => Some(NodePath::root()),
+            (parent, _) => Some(NodePath(String::from(parent))),
+        }
+    }
+
+    /// The path of the child `component` of this node.
+    ///
+    /// Fails if `component` is not a valid node name.
+    pub fn push(&self, component: &str) -> PathResult<NodePath> {
+        check_component(component)?;
+
+        let mut path = self.0.clone();
+        if path != "/" {
+            path.push('/');
+        }
+        path.push_str(component);
+        Ok(NodePath(path))
+    }
+}
+
+fn check_component(component: &str) -> PathResult<()> {
+    if component.is_empty() {
+        return Err(PathError::EmptyComponent);
+    }
+    if !is_valid_node_name(component) {
+        return Err(PathError::InvalidComponent);
+    }
+
+    Ok(())
+}
+
+impl AsRef<str> for NodePath {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// What a `path:property` spec refers to, as returned by
+/// [`DeviceTree::get`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TreeEntry<'a> {
+    /// A spec without a property, such as `/soc`.
+    Node(&'a Node),
+
+    /// A spec with a property, such as `/soc:compatible`.
+    Property(&'a Property),
+}
+
+impl<'a> TreeEntry<'a> {
+    /// The node, if the spec did not name a property.
+    pub fn node(&self) -> Option<&'a Node> {
+        match self {
+            TreeEntry::Node(node) => Some(node),
+            TreeEntry::Property(_) => None,
+        }
+    }
+
+    /// The property, if the spec named one.
+    pub fn property(&self) -> Option<&'a Property> {
+        match self {
+            TreeEntry::Node(_) => None,
+            TreeEntry::Property(prop) => Some(prop),
+        }
+    }
+}
+
+impl DeviceTree {
+    /// Look up a node or property by a single spec, as used by U-Boot's
+    /// `fdt` command: a node path, optionally followed by `:` and the name
+    /// of a property, e.g. `/soc/uart@7e201000:clocks` or `/:model`.
+    ///
+    /// The path may start with an alias, as in
+    /// [`find_node`](DeviceTree::find_node). Neither node nor property
+    /// names can contain `:`, so the spec is split at the first one, and
+    /// the value of the property does not matter. Returns `None` if there
+    /// is no such node or property, or the property name is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// let model = dt.get("/:model").unwrap().property().unwrap();
+    /// assert_eq!(model.as_str(), Some("Raspberry Pi 2 Model B"));
+    ///
+    /// let soc = dt.get("/soc").unwrap().node().unwrap();
+    /// assert_eq!(soc.name(), "soc");
+    /// ```
+    pub fn get<'a>(&'a self, spec: &str) -> Option<TreeEntry<'a>> {
+        let Some((path, name)) = spec.split_once(':') else {
+            return self.find_node(spec).map(TreeEntry::Node);
+        };
+
+        let prop = self.find_node(path)?.property(name)?;
+        Some(TreeEntry::Property(prop))
+    }
+}
@@ -0,0 +1,583 @@
+use alloc::{borrow::ToOwned, string::String, vec, vec::Vec};
+use hashbrown::HashMap;
+
+use crate::{
+    child_path, DeviceTree, Node, NodePath, PropError, Property, Result,
+    TreeError, TreeResult,
+};
+
+/// Index of the nodes of a tree by phandle.
+///
+/// Created by [`DeviceTree::phandle_map`].
+#[derive(Debug)]
+pub struct PhandleMap<'a> {
+    nodes: HashMap<u32, &'a Node>,
+    duplicates: Vec<u32>,
+}
+
+impl<'a> PhandleMap<'a> {
+    /// The node with the given phandle.
+    ///
+    /// If several nodes share the phandle, this is the first one in
+    /// depth-first order.
+    pub fn get(&self, phandle: u32) -> Option<&'a Node> {
+        self.nodes.get(&phandle).copied()
+    }
+
+    /// The number of distinct phandles.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether no node has a phandle.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The phandles used by more than one node, in the order the second
+    /// use was found. A phandle used by `n` nodes appears `n - 1` times.
+    ///
+    /// Phandles must be unique, so this is empty for well-formed trees.
+    pub fn duplicates(&self) -> &[u32] {
+        &self.duplicates
+    }
+}
+
+/// Index of the paths of the nodes of a tree by phandle.
+///
+/// Unlike [`PhandleMap`], this does not borrow the tree, so it can be kept
+/// while the tree is changed. Created by
+/// [`DeviceTree::build_phandle_index`].
+#[derive(Clone, Debug, Default)]
+pub struct PhandleIndex {
+    paths: HashMap<u32, NodePath>,
+    duplicates: Vec<u32>,
+}
+
+impl PhandleIndex {
+    /// The path of the node with the given phandle.
+    ///
+    /// If several nodes share the phandle, this is the first one in
+    /// depth-first order.
+    pub fn get(&self, phandle: u32) -> Option<&NodePath> {
+        self.paths.get(&phandle)
+    }
+
+    /// The number of distinct phandles.
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Whether no node has a phandle.
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty()
+    }
+
+    /// Iterate over the phandles and the paths of their nodes, in no
+    /// particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &NodePath)> {
+        self.paths.iter().map(|(phandle, path)| (*phandle, path))
+    }
+
+    /// The phandles used by more than one node, as in
+    /// [`PhandleMap::duplicates`].
+    pub fn duplicates(&self) -> &[u32] {
+        &self.duplicates
+    }
+}
+
+/// Index `entries` by phandle, keeping the first entry for each phandle and
+/// recording the phandles of the others as duplicates.
+fn index_by_phandle<T>(
+    entries: impl Iterator<Item = (u32, T)>,
+) -> (HashMap<u32, T>, Vec<u32>) {
+    let mut index = HashMap::new();
+    let mut duplicates = Vec::new();
+
+    for (phandle, entry) in entries {
+        if index.try_insert(phandle, entry).is_err() {
+            duplicates.push(phandle);
+        }
+    }
+
+    (index, duplicates)
+}
+
+/// Properties holding a list of phandles, each followed by the number of
+/// argument cells given by the named property of the node it refers to.
+/// `None` means there are no arguments.
+const REFERENCE_PROPERTIES: &[(&str, Option<&str>)] = &[
+    ("interrupt-parent", None),
+    ("clocks", Some("#clock-cells")),
+    ("resets", Some("#reset-cells")),
+    ("gpios", Some("#gpio-cells")),
+];
+
+/// Properties that hold cells, but never phandles.
+const NON_REFERENCE_PROPERTIES: &[&str] =
+    &["phandle", "linux,phandle", "reg", "ranges", "dma-ranges"];
+
+/// An entry of a phandle list, such as `clocks`.
+///
+/// Created by [`DeviceTree::parse_phandle_args`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhandleArgs<'a> {
+    /// The node referred to.
+    pub target: &'a Node,
+
+    /// The argument cells following the phandle.
+    pub args: Vec<u32>,
+}
+
+/// A property referring to a node by phandle.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reference {
+    /// The path of the node holding the property.
+    pub path: String,
+
+    /// The name of the property.
+    pub property: String,
+
+    /// The argument cells following the phandle.
+    pub args: Vec<u32>,
+}
+
+/// Index of the properties referring to each phandle.
+///
+/// Created by [`DeviceTree::phandle_references`].
+#[derive(Debug, Default)]
+pub struct ReferenceIndex {
+    references: HashMap<u32, Vec<Reference>>,
+}
+
+impl ReferenceIndex {
+    /// The references to `phandle`, in document order.
+    pub fn references_to(&self, phandle: u32) -> &[Reference] {
+        self.references.get(&phandle).map_or(&[], Vec::as_slice)
+    }
+
+    fn add(&mut self, phandle: u32, path: &str, prop: &Property, args: &[u32]) {
+        self.references.entry(phandle).or_default().push(Reference {
+            path: path.to_owned(),
+            property: prop.name.clone(),
+            args: args.to_vec(),
+        });
+    }
+}
+
+/// The argument count property for a property known to hold references.
+pub(crate) fn reference_cells(name: &str) -> Option<Option<&'static str>> {
+    if name.ends_with("-supply") {
+        return Some(None);
+    }
+    if name.ends_with("-gpios") {
+        return Some(Some("#gpio-cells"));
+    }
+
+    REFERENCE_PROPERTIES
+        .iter()
+        .find(|(prop, _)| *prop == name)
+        .map(|(_, cells)| *cells)
+}
+
+impl Node {
+    /// The phandle of this node, from `phandle` or the older
+    /// `linux,phandle` property.
+    ///
+    /// Returns `None` if neither property is a single cell.
+    pub fn phandle_value(&self) -> Option<u32> {
+        self.property_u32("phandle")
+            .or_else(|| self.property_u32("linux,phandle"))
+    }
+
+    /// Set the `phandle` property of this node, so that other nodes can
+    /// refer to it as `value`.
+    ///
+    /// Any `linux,phandle` property is removed, so that the node does not
+    /// end up with two different phandles.
+    pub fn set_phandle(&mut self, value: u32) {
+        let data = value.to_be_bytes().to_vec();
+        match self.property_mut("phandle") {
+            Some(prop) => prop.data = data,
+            None => self.props.push(Property::new("phandle", data)),
+        }
+        self.remove_property("linux,phandle");
+    }
+
+    fn find_phandle(&self, phandle: u32) -> Option<&Node> {
+        if self.phandle_value() == Some(phandle) {
+            return Some(self);
+        }
+
+        self.children.iter().find_map(|n| n.find_phandle(phandle))
+    }
+
+    fn index_references(
+        &self,
+        path: &str,
+        phandles: &PhandleMap,
+        index: &mut ReferenceIndex,
+    ) {
+        for prop in self.props.iter() {
+            if prop.is_empty() || !prop.is_cell_aligned() {
+                continue;
+            }
+            let cells: Vec<u32> = prop.cells().collect();
+
+            match reference_cells(&prop.name) {
+                Some(cells_name) => {
+                    // record the entries up to the first malformed one
+                    let _ = walk_phandle_list(
+                        &cells,
+                        cells_name,
+                        |phandle| phandles.get(phandle),
+                        |_, phandle, _, args| {
+                            index.add(phandle, path, prop, args)
+                        },
+                    );
+                }
+                None => {
+                    if prop.name.starts_with('#')
+                        || NON_REFERENCE_PROPERTIES
+                            .contains(&prop.name.as_str())
+                    {
+                        continue;
+                    }
+                    if phandles.get(cells[0]).is_some() {
+                        index.add(cells[0], path, prop, &cells[1..]);
+                    }
+                }
+            }
+        }
+
+        for child in self.children.iter() {
+            let path = child_path(path, &child.name);
+            child.index_references(&path, phandles, index);
+        }
+    }
+}
+
+/// Split a list of phandles, each followed by the number of argument cells
+/// given by the `cells_name` property of the node it refers to, and call
+/// `entry` with the index of the phandle cell, the phandle, the node and the
+/// arguments of each entry.
+///
+/// Entries with a phandle of `0` are empty and skipped. If `cells_name` is
+/// `None`, entries have no arguments.
+pub(crate) fn walk_phandle_list<'a>(
+    cells: &[u32],
+    cells_name: Option<&str>,
+    lookup: impl Fn(u32) -> Option<&'a Node>,
+    mut entry: impl FnMut(usize, u32, &'a Node, &[u32]),
+) -> core::result::Result<(), PropError> {
+    let mut index = 0;
+    while let Some(&phandle) = cells.get(index) {
+        let rest = &cells[index + 1..];
+        if phandle == 0 {
+            index += 1;
+            continue;
+        }
+
+        let target =
+            lookup(phandle).ok_or(PropError::UnknownPhandle(phandle))?;
+        let count = match cells_name {
+            Some(name) => target
+                .property_u32(name)
+                .ok_or(PropError::MissingCellCount)?,
+            None => 0,
+        } as usize;
+        if count > rest.len() {
+            return Err(PropError::Truncated);
+        }
+
+        entry(index, phandle, target, &rest[..count]);
+        index += 1 + count;
+    }
+
+    Ok(())
+}
+
+impl DeviceTree {
+    /// Find the node with the given phandle.
+    ///
+    /// This walks the whole tree; use
+    /// [`build_phandle_index`](DeviceTree::build_phandle_index) for repeated
+    /// lookups.
+    pub fn find_by_phandle(&self, phandle: u32) -> Option<&Node> {
+        self.root.find_phandle(phandle)
+    }
+
+    /// Index every node with a `phandle` or `linux,phandle` property, for
+    /// repeated lookups without walking the tree.
+    ///
+    /// Nodes reusing a phandle that was already seen are not indexed, but
+    /// recorded in [`PhandleMap::duplicates`].
+    pub fn phandle_map(&self) -> PhandleMap<'_> {
+        let (nodes, duplicates) = index_by_phandle(
+            self.iter_dfs()
+                .filter_map(|node| Some((node.phandle_value()?, node))),
+        );
+
+        PhandleMap { nodes, duplicates }
+    }
+
+    /// Decode the property `prop_name` of `node` as a list of phandles, each
+    /// followed by the number of argument cells given by the
+    /// `cells_prop_name` property (such as `#clock-cells`) of the node
+    /// referred to.
+    ///
+    /// Entries with a phandle of `0` are empty and skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// let led = dt.find("/soc/leds/act").unwrap();
+    /// let gpios = dt.parse_phandle_args(led, "gpios", "#gpio-cells").unwrap();
+    /// assert_eq!(gpios[0].target.name(), "gpio@7e200000");
+    /// assert_eq!(gpios[0].args, [47, 0]);
+    /// ```
+    pub fn parse_phandle_args<'a>(
+        &'a self,
+        node: &Node,
+        prop_name: &str,
+        cells_prop_name: &str,
+    ) -> Result<Vec<PhandleArgs<'a>>> {
+        let prop = node.property(prop_name).ok_or(PropError::NotFound)?;
+        if !prop.is_cell_aligned() {
+            return Err(PropError::NotCellAligned.into());
+        }
+
+        let cells: Vec<u32> = prop.cells().collect();
+        let mut list = Vec::new();
+        walk_phandle_list(
+            &cells,
+            Some(cells_prop_name),
+            |phandle| self.find_by_phandle(phandle),
+            |_, _, target, args| {
+                list.push(PhandleArgs {
+                    target,
+                    args: args.to_vec(),
+                })
+            },
+        )?;
+
+        Ok(list)
+    }
+
+    /// Index which properties refer to which phandles, to find out what
+    /// uses a node.
+    ///
+    /// References are found in two ways:
+    ///
+    /// - `interrupt-parent`, `*-supply`, `clocks`, `resets`, `gpios` and
+    ///   `*-gpios` are decoded exactly as lists of phandles, each followed by
+    ///   the number of arguments given by the `#clock-cells`, `#reset-cells`
+    ///   or `#gpio-cells` of the node referred to. Decoding stops at the
+    ///   first malformed entry, as
+    ///   [`parse_phandle_args`](DeviceTree::parse_phandle_args) would
+    ///   report it.
+    /// - For any other property holding cells, if the first cell is the
+    ///   phandle of a node, it is taken as a reference with the remaining
+    ///   cells as arguments. This is a guess and may find references that
+    ///   are just numbers. `reg`, `ranges`, `dma-ranges`, phandles and
+    ///   `#`-prefixed properties are never considered.
+    pub fn phandle_references(&self) -> ReferenceIndex {
+        let phandles = self.phandle_map();
+        let mut index = ReferenceIndex::default();
+        self.root.index_references("/", &phandles, &mut index);
+        index
+    }
+
+    /// Index the path of every node with a `phandle` or `linux,phandle`
+    /// property, for repeated lookups without walking the tree.
+    ///
+    /// Duplicate phandles are handled as by
+    /// [`phandle_map`](DeviceTree::phandle_map).
+    pub fn build_phandle_index(&self) -> PhandleIndex {
+        let (paths, duplicates) = index_by_phandle(
+            self.iter_with_paths().filter_map(|(path, node)| {
+                Some((node.phandle_value()?, NodePath::from_tree(path)))
+            }),
+        );
+
+        PhandleIndex { paths, duplicates }
+    }
+}
+
+impl DeviceTree {
+    /// The highest phandle defined by a `phandle` or `linux,phandle`
+    /// property, or `0` if there is none.
+    ///
+    /// Phandles from `max_phandle() + 1` on are free, e.g. as the `base`
+    /// for [`fixup_phandles`](DeviceTree::fixup_phandles) on an overlay.
+    pub fn max_phandle(&self) -> u32 {
+        self.defined_phandles().max().unwrap_or(0)
+    }
+
+    /// Every phandle defined by a `phandle` or `linux,phandle` property,
+    /// sorted and without duplicates.
+    ///
+    /// Phandles that are only referred to are not included.
+    pub fn all_phandles(&self) -> Vec<u32> {
+        let mut phandles: Vec<_> = self.defined_phandles().collect();
+        phandles.sort_unstable();
+        phandles.dedup();
+        phandles
+    }
+
+    /// The value of every `phandle` and `linux,phandle` property.
+    fn defined_phandles(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter().flat_map(|node| {
+            ["phandle", "linux,phandle"]
+                .into_iter()
+                .filter_map(|name| node.property_u32(name))
+        })
+    }
+
+    /// Add `base` to the phandle of every node and to every reference to
+    /// one, e.g. to make room for the phandles of another tree.
+    ///
+    /// References are found in the properties whose format is known, such
+    /// as `interrupt-parent`, `clocks`, `*-gpios` and `*-supply`. The
+    /// argument cells following each phandle are left alone. Entries that
+    /// cannot be decoded, such as references to phandles no node has, end
+    /// the list they are in. Use
+    /// [`fixup_phandles_explicit`](DeviceTree::fixup_phandles_explicit)
+    /// for other properties.
+    ///
+    /// Fails without changing the tree if a phandle would reach
+    /// `0xffffffff`.
+    pub fn fixup_phandles(&mut self, base: u32) -> TreeResult<()> {
+        self.renumber_phandles(base, reference_cells)
+    }
+
+    /// Like [`fixup_phandles`](DeviceTree::fixup_phandles), but only update
+    /// references in the properties named in `reference_properties`.
+    ///
+    /// Listed properties of a known format are decoded as in
+    /// [`fixup_phandles`](DeviceTree::fixup_phandles). Every cell of any
+    /// other listed property is taken to be a phandle.
+    pub fn fixup_phandles_explicit(
+        &mut self,
+        base: u32,
+        reference_properties: &[&str],
+    ) -> TreeResult<()> {
+        self.renumber_phandles(base, |name| {
+            if !reference_properties.contains(&name) {
+                return None;
+            }
+            Some(reference_cells(name).unwrap_or(None))
+        })
+    }
+
+    /// Add `base` to every phandle and to the references found in the
+    /// properties for which `cells_of` returns the argument count property.
+    fn renumber_phandles(
+        &mut self,
+        base: u32,
+        cells_of: impl Fn(&str) -> Option<Option<&'static str>>,
+    ) -> TreeResult<()> {
+        // find every phandle and reference before changing anything, as
+        // decoding references depends on the nodes they point to, and check
+        // them all so that the tree is left alone on failure
+        let cells = self.phandle_cell_indices(cells_of);
+        for (path, name, indices) in cells.iter() {
+            let Some(prop) = self.find(path).and_then(|n| n.property(name))
+            else {
+                continue;
+            };
+            for &index in indices {
+                if read_cell(prop, index)
+                    .is_some_and(|cell| shift_phandle(cell, base).is_none())
+                {
+                    return Err(TreeError::PhandleOverflow);
+                }
+            }
+        }
+
+        for (path, name, indices) in cells {
+            let node = self.find_mut(&path);
+            if let Some(prop) = node.and_then(|node| node.property_mut(&name)) {
+                for index in indices {
+                    add_to_cell(prop, index, base)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The path of the node, the name of the property and the indices of
+    /// the phandle cells of every `phandle` and `linux,phandle` property,
+    /// and of every property for which `cells_of` returns the argument
+    /// count property.
+    fn phandle_cell_indices(
+        &self,
+        cells_of: impl Fn(&str) -> Option<Option<&'static str>>,
+    ) -> Vec<(String, String, Vec<usize>)> {
+        let phandles = self.phandle_map();
+        let mut references = Vec::new();
+
+        for (path, node) in self.iter_with_paths() {
+            for prop in node.props.iter() {
+                if prop.name == "phandle" || prop.name == "linux,phandle" {
+                    references.push((path.clone(), prop.name.clone(), vec![0]));
+                    continue;
+                }
+                let Some(cells_name) = cells_of(&prop.name) else {
+                    continue;
+                };
+                if !prop.is_cell_aligned() {
+                    continue;
+                }
+
+                let cells: Vec<u32> = prop.cells().collect();
+                let mut indices = Vec::new();
+                let _ = walk_phandle_list(
+                    &cells,
+                    cells_name,
+                    |phandle| phandles.get(phandle),
+                    |index, _, _, _| indices.push(index),
+                );
+                if !indices.is_empty() {
+                    references.push((path.clone(), prop.name.clone(), indices));
+                }
+            }
+        }
+
+        references
+    }
+}
+
+/// The cell at `index` of the value of `prop`, if the value is long enough.
+fn read_cell(prop: &Property, index: usize) -> Option<u32> {
+    let cell = prop.data.get(index * 4..index * 4 + 4)?;
+    Some(u32::from_be_bytes([cell[0], cell[1], cell[2], cell[3]]))
+}
+
+/// Add `delta` to the cell at `index` of the value of `prop`.
+fn add_to_cell(
+    prop: &mut Property,
+    index: usize,
+    delta: u32,
+) -> TreeResult<()> {
+    if let Some(value) = read_cell(prop, index) {
+        let value =
+            shift_phandle(value, delta).ok_or(TreeError::PhandleOverflow)?;
+        prop.data[index * 4..index * 4 + 4]
+            .copy_from_slice(&value.to_be_bytes());
+    }
+
+    Ok(())
+}
+
+/// Add `delta` to `phandle`, unless that reaches `0xffffffff`, which is not
+/// a valid phandle.
+fn shift_phandle(phandle: u32, delta: u32) -> Option<u32> {
+    phandle.checked_add(delta).filter(|&p| p != u32::MAX)
+}
@@ -0,0 +1,447 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+use core::{slice::ChunksExact, str};
+
+use crate::util::SliceRead;
+
+/// Properties whose values are always cells, even if they happen to look like
+/// strings.
+const CELL_PROPERTIES: &[&str] = &[
+    "#address-cells",
+    "#size-cells",
+    "#interrupt-cells",
+    "#clock-cells",
+    "#gpio-cells",
+    "phandle",
+    "linux,phandle",
+    "interrupt-parent",
+    "reg",
+    "ranges",
+    "dma-ranges",
+];
+
+/// The value of a property, classified by what its contents look like.
+///
+/// Created by [`Property::value`], or by [`Property::decode_as`] if the type
+/// is known.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PropertyValue<'a> {
+    /// An empty value, as used for flags.
+    Empty,
+
+    /// A single string.
+    String(&'a str),
+
+    /// Several strings.
+    StringList(Vec<&'a str>),
+
+    /// A single cell.
+    U32(u32),
+
+    /// A 64-bit number made up of two cells. Only returned by
+    /// [`Property::decode_as`].
+    U64(u64),
+
+    /// Several cells.
+    Cells(Vec<u32>),
+
+    /// Anything else.
+    Bytes(&'a [u8]),
+
+    /// A reference to another node.
+    Phandle(u32),
+}
+
+/// Short name for [`PropertyValue`].
+pub type PropValue<'a> = PropertyValue<'a>;
+
+/// Properties whose value is a single phandle.
+const PHANDLE_PROPERTIES: &[&str] =
+    &["phandle", "linux,phandle", "interrupt-parent"];
+
+/// The kinds of [`PropertyValue`], used to tell [`Property::decode_as`]
+/// how to read a value.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PropHint {
+    Empty,
+    U32,
+    U64,
+    String,
+    StringList,
+    Cells,
+    Bytes,
+    Phandle,
+}
+
+/// A single property of a device tree node.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Property {
+    /// The name of the property.
+    pub(crate) name: String,
+
+    /// The raw value of the property.
+    ///
+    /// With the `serde` feature, this is serialized as a string of hex
+    /// digits.
+    #[cfg_attr(feature = "serde", serde(with = "hex"))]
+    pub(crate) data: Vec<u8>,
+
+    /// Offset of the value in the blob the property was loaded from.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) data_offset: Option<usize>,
+}
+
+// Where a property was loaded from is not part of its contents, so equal
+// properties from different blobs, or built in code, compare equal.
+impl PartialEq for Property {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.data == other.data
+    }
+}
+
+impl Eq for Property {}
+
+impl Property {
+    /// Create a property with the given name and raw value.
+    pub fn new(name: &str, data: Vec<u8>) -> Property {
+        Property {
+            name: name.to_owned(),
+            data,
+            data_offset: None,
+        }
+    }
+
+    /// The name of the property, without its NUL terminator.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The raw value of the property.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Replace the raw value of the property.
+    pub fn set_data(&mut self, data: Vec<u8>) {
+        self.data = data;
+    }
+
+    /// Offset of the value in the blob the tree was loaded from, e.g. to
+    /// patch it in place.
+    ///
+    /// Returns `None` for properties that were not loaded from a blob. The
+    /// offset is not updated when the value is changed, or when the tree is
+    /// stored again.
+    pub fn data_offset(&self) -> Option<usize> {
+        self.data_offset
+    }
+
+    /// Classify the value using the same heuristics as `dtc`.
+    ///
+    /// Values made up of printable, NUL-terminated strings are strings,
+    /// values of whole cells are cells and anything else is bytes.
+    /// Well-known properties such as `reg` and `phandle` are always
+    /// treated as cells, and a single cell of `phandle`, `linux,phandle` or
+    /// `interrupt-parent` as a phandle.
+    ///
+    /// A value of two cells could just as well be a 64-bit number as a pair
+    /// of cells, so it is returned as cells; use
+    /// [`decode_as`](Property::decode_as) with [`PropHint::U64`] if the
+    /// type is known.
+    pub fn value(&self) -> PropertyValue<'_> {
+        if self.data.is_empty() {
+            return PropertyValue::Empty;
+        }
+
+        let cells = self.is_cell_aligned();
+        if !(cells && CELL_PROPERTIES.contains(&self.name.as_str()))
+            && is_string_list(&self.data)
+        {
+            let mut strings: Vec<_> = self.strings().collect();
+            return match strings.len() {
+                1 => PropertyValue::String(strings.remove(0)),
+                _ => PropertyValue::StringList(strings),
+            };
+        }
+
+        match (cells, self.data.len()) {
+            (true, 4) if PHANDLE_PROPERTIES.contains(&self.name.as_str()) => {
+                PropertyValue::Phandle(self.cells().next().unwrap_or(0))
+            }
+            (true, 4) => PropertyValue::U32(self.cells().next().unwrap_or(0)),
+            (true, _) => PropertyValue::Cells(self.cells().collect()),
+            (false, _) => PropertyValue::Bytes(&self.data),
+        }
+    }
+
+    /// Guess the type of the value; the same as [`value`](Property::value).
+    pub fn infer_value(&self) -> PropValue<'_> {
+        self.value()
+    }
+
+    /// Read the value as the given type.
+    ///
+    /// Returns `None` if the value does not have the size or format the
+    /// type requires: e.g. exactly 4 bytes for [`PropHint::U32`] and
+    /// [`PropHint::Phandle`], one or two cells for [`PropHint::U64`], or
+    /// NUL-terminated UTF-8 strings for [`PropHint::String`] and
+    /// [`PropHint::StringList`]. Any value can be read as
+    /// [`PropHint::Bytes`].
+    pub fn decode_as(&self, hint: PropHint) -> Option<PropertyValue<'_>> {
+        match hint {
+            PropHint::Empty => self.is_empty().then_some(PropertyValue::Empty),
+            PropHint::U32 => self.as_u32().map(PropertyValue::U32),
+            PropHint::U64 => self.as_u64().map(PropertyValue::U64),
+            PropHint::String => {
+                let raw = self.data.strip_suffix(&[0])?;
+                if raw.contains(&0) {
+                    return None;
+                }
+                Some(PropertyValue::String(str::from_utf8(raw).ok()?))
+            }
+            PropHint::StringList => {
+                let raw = self.data.strip_suffix(&[0])?;
+                let strings = raw
+                    .split(|&b| b == 0)
+                    .map(|s| str::from_utf8(s).ok())
+                    .collect::<Option<_>>()?;
+                Some(PropertyValue::StringList(strings))
+            }
+            PropHint::Cells => self.as_u32_array().map(PropertyValue::Cells),
+            PropHint::Bytes => Some(PropertyValue::Bytes(&self.data)),
+            PropHint::Phandle => self.as_u32().map(PropertyValue::Phandle),
+        }
+    }
+
+    /// Whether the value is empty, as for flags such as
+    /// `interrupt-controller`.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The value as a single big-endian `u32`.
+    ///
+    /// Returns `None` unless the value is exactly 4 bytes long.
+    pub fn as_u32(&self) -> Option<u32> {
+        if self.data.len() != 4 {
+            return None;
+        }
+
+        self.data.as_slice().read_be_u32(0).ok()
+    }
+
+    /// The value as a big-endian `u64` made up of one or two cells.
+    ///
+    /// A single cell is zero-extended, two cells are read most significant
+    /// cell first. Returns `None` for values of any other length.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self.data.len() {
+            4 | 8 => read_cells(&self.data),
+            _ => None,
+        }
+    }
+
+    /// The number of complete 32-bit cells in the value.
+    pub fn cell_count(&self) -> usize {
+        self.data.len() / 4
+    }
+
+    /// The value as a NUL-terminated string.
+    ///
+    /// Only the string up to the first NUL is returned, so for string lists
+    /// such as `compatible` this is the first entry. Returns `None` if the
+    /// value has no terminator or is not valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        let end = self.data.iter().position(|&b| b == 0)?;
+
+        str::from_utf8(&self.data[..end]).ok()
+    }
+
+    /// The value as a list of NUL-separated strings, such as `compatible`.
+    ///
+    /// This is the same as [`strings`](Property::strings).
+    pub fn as_strlist(&self) -> Strings<'_> {
+        self.strings()
+    }
+
+    /// Iterate over the NUL-terminated strings making up the value.
+    ///
+    /// Iteration ends early, without yielding it, at an entry that is missing
+    /// its terminator or is not valid UTF-8.
+    pub fn strings(&self) -> Strings<'_> {
+        Strings { data: &self.data }
+    }
+
+    /// Whether one of the strings making up the value equals `s`.
+    pub fn contains_string(&self, s: &str) -> bool {
+        self.strings().any(|entry| entry == s)
+    }
+
+    /// Iterate over the complete big-endian `u32` cells of the value.
+    ///
+    /// Trailing bytes that do not make up a whole cell are ignored, see
+    /// [`is_cell_aligned`](Property::is_cell_aligned).
+    pub fn cells(&self) -> Cells<'_> {
+        Cells {
+            chunks: self.data.chunks_exact(4),
+        }
+    }
+
+    /// The cell at the given index.
+    pub fn cell(&self, index: usize) -> Option<u32> {
+        self.data.as_slice().read_be_u32(index.checked_mul(4)?).ok()
+    }
+
+    /// Whether the value is made up of whole cells.
+    pub fn is_cell_aligned(&self) -> bool {
+        self.data.len().is_multiple_of(4)
+    }
+
+    /// The value as an array of big-endian `u32` cells.
+    ///
+    /// Returns `None` if the length of the value is not a multiple of 4.
+    pub fn as_u32_array(&self) -> Option<Vec<u32>> {
+        if !self.is_cell_aligned() {
+            return None;
+        }
+
+        Some(self.cells().collect())
+    }
+
+    /// The value as a list of `(address, size)` pairs, such as `reg`.
+    ///
+    /// `addr_cells` and `size_cells` are the `#address-cells` and
+    /// `#size-cells` of the parent node. Values of up to 2 cells are
+    /// supported. Returns `None` if the length of the value is not a
+    /// multiple of the entry size.
+    pub fn as_reg(
+        &self,
+        addr_cells: u32,
+        size_cells: u32,
+    ) -> Option<Vec<(u64, u64)>> {
+        if addr_cells > 2 || size_cells > 2 || addr_cells + size_cells == 0 {
+            return None;
+        }
+
+        let stride = (addr_cells + size_cells) as usize * 4;
+        if !self.data.len().is_multiple_of(stride) {
+            return None;
+        }
+
+        self.data
+            .chunks_exact(stride)
+            .map(|entry| {
+                let (address, size) = entry.split_at(addr_cells as usize * 4);
+                Some((read_cells(address)?, read_cells(size)?))
+            })
+            .collect()
+    }
+}
+
+/// Serialization of property values as strings of hex digits.
+#[cfg(feature = "serde")]
+mod hex {
+    use alloc::{string::String, vec::Vec};
+    use core::fmt::Write;
+
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        data: &[u8],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut hex = String::with_capacity(data.len() * 2);
+        for byte in data {
+            // writing to a String cannot fail
+            let _ = write!(hex, "{:02x}", byte);
+        }
+        serializer.serialize_str(&hex)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<u8>, D::Error> {
+        let hex = String::deserialize(deserializer)?;
+        if !hex.is_ascii() || !hex.len().is_multiple_of(2) {
+            return Err(D::Error::custom("invalid hex string"));
+        }
+
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| {
+                u8::from_str_radix(&hex[i..i + 2], 16)
+                    .map_err(|_| D::Error::custom("invalid hex string"))
+            })
+            .collect()
+    }
+}
+
+/// Whether `data` is a list of non-empty, printable, NUL-terminated strings.
+fn is_string_list(data: &[u8]) -> bool {
+    match data.strip_suffix(&[0]) {
+        Some(raw) => raw.split(|&b| b == 0).all(|s| {
+            !s.is_empty()
+                && s.iter().all(|&b| b == b' ' || b.is_ascii_graphic())
+        }),
+        None => false,
+    }
+}
+
+/// Read a value made up of big-endian cells, most significant cell first.
+///
+/// Returns `None` if the value does not fit into a `u64`.
+pub(crate) fn read_cells(mut raw: &[u8]) -> Option<u64> {
+    if raw.len() > 8 || !raw.len().is_multiple_of(4) {
+        return None;
+    }
+
+    let mut val = 0;
+    while !raw.is_empty() {
+        val = (val << 32) | raw.read_be_u32(0).ok()? as u64;
+        raw = &raw[4..];
+    }
+
+    Some(val)
+}
+
+/// Iterator over the cells of a property.
+///
+/// Created by [`Property::cells`].
+pub struct Cells<'a> {
+    chunks: ChunksExact<'a, u8>,
+}
+
+impl Iterator for Cells<'_> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        let cell = self.chunks.next()?;
+        Some(u32::from_be_bytes([cell[0], cell[1], cell[2], cell[3]]))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+/// Iterator over the strings of a string list property.
+///
+/// Created by [`Property::strings`].
+pub struct Strings<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for Strings<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let data = core::mem::take(&mut self.data);
+
+        let end = data.iter().position(|&b| b == 0)?;
+        let s = str::from_utf8(&data[..end]).ok()?;
+
+        self.data = &data[end + 1..];
+        Some(s)
+    }
+}
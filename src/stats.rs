@@ -0,0 +1,82 @@
+use core::fmt;
+use hashbrown::HashSet;
+
+use crate::DeviceTree;
+
+/// Summary of the size of a tree.
+///
+/// Created by [`DeviceTree::stats`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TreeStats {
+    /// The number of nodes, including the root node.
+    pub nodes: usize,
+
+    /// The number of properties.
+    pub properties: usize,
+
+    /// The depth of the deepest node, with the root node at depth `0`.
+    pub max_depth: usize,
+
+    /// The total length of all property values in bytes.
+    pub property_bytes: usize,
+
+    /// The number of distinct property names.
+    pub property_names: usize,
+
+    /// The size of the structure block according to the header of the blob
+    /// the tree was loaded from, or `0` for a tree that was not loaded.
+    pub struct_size: u32,
+
+    /// The size of the strings block according to the header of the blob
+    /// the tree was loaded from, or `0` for a tree that was not loaded.
+    pub strings_size: u32,
+}
+
+impl fmt::Display for TreeStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "nodes: {}", self.nodes)?;
+        writeln!(f, "properties: {}", self.properties)?;
+        writeln!(f, "max depth: {}", self.max_depth)?;
+        writeln!(f, "property bytes: {}", self.property_bytes)?;
+        writeln!(f, "property names: {}", self.property_names)?;
+        writeln!(f, "struct block: {} bytes", self.struct_size)?;
+        write!(f, "strings block: {} bytes", self.strings_size)
+    }
+}
+
+impl DeviceTree {
+    /// Count the nodes and properties of the tree in a single walk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// println!("{}", dt.stats());
+    /// ```
+    pub fn stats(&self) -> TreeStats {
+        let mut names = HashSet::new();
+        let mut stats = TreeStats {
+            struct_size: self.header().size_dt_struct,
+            strings_size: self.header().size_dt_strings,
+            ..TreeStats::default()
+        };
+
+        for (depth, node) in self.iter_dfs_with_depth() {
+            stats.nodes += 1;
+            stats.max_depth = stats.max_depth.max(depth);
+
+            for prop in node.props.iter() {
+                stats.properties += 1;
+                stats.property_bytes += prop.data.len();
+                names.insert(prop.name.as_str());
+            }
+        }
+
+        stats.property_names = names.len();
+        stats
+    }
+}
@@ -0,0 +1,52 @@
+use alloc::{borrow::ToOwned, string::String, vec::Vec};
+
+use crate::Node;
+
+/// The operational status of a device, from its `status` property.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum NodeStatus {
+    /// The device is operational. This is the default if there is no
+    /// `status` property.
+    Okay,
+
+    /// The device is not operational, but might become so.
+    Disabled,
+
+    /// The device is operational, but should not be used, e.g. because it
+    /// is controlled by other software.
+    Reserved,
+
+    /// The device is not operational because of a serious error. Holds the
+    /// error condition following `fail-`, which may be empty.
+    Fail(String),
+
+    /// A value not covered by the specification, as raw bytes.
+    Unknown(Vec<u8>),
+}
+
+impl Node {
+    /// The status of the device, from the `status` property.
+    pub fn status(&self) -> NodeStatus {
+        let prop = match self.property("status") {
+            Some(prop) => prop,
+            None => return NodeStatus::Okay,
+        };
+
+        match prop.as_str() {
+            Some("okay" | "ok") => NodeStatus::Okay,
+            Some("disabled") => NodeStatus::Disabled,
+            Some("reserved") => NodeStatus::Reserved,
+            Some("fail") => NodeStatus::Fail(String::new()),
+            Some(s) if s.starts_with("fail-") => {
+                NodeStatus::Fail(s["fail-".len()..].to_owned())
+            }
+            _ => NodeStatus::Unknown(prop.data.clone()),
+        }
+    }
+
+    /// Whether the device should be used, i.e. it has no `status` property
+    /// or its status is `okay` or `ok`.
+    pub fn is_enabled(&self) -> bool {
+        self.status() == NodeStatus::Okay
+    }
+}
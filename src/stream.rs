@@ -0,0 +1,144 @@
+use core::str;
+
+use crate::util::{align, SliceRead};
+use crate::{
+    skip_nops, DeviceTreeHeader, Error, Result, OF_DT_BEGIN_NODE, OF_DT_END,
+    OF_DT_END_NODE, OF_DT_PROP, SUPPORTED_VERSION,
+};
+
+/// An item of the structure block, as yielded by [`StreamingParser`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ParseEvent<'a> {
+    /// The start of a node, followed by its properties and children.
+    BeginNode { name: &'a str },
+
+    /// A property of the innermost open node.
+    Property { name: &'a str, data: &'a [u8] },
+
+    /// The end of the innermost open node.
+    EndNode,
+
+    /// The end of the structure block. Always the last event.
+    End,
+}
+
+/// Parser yielding the contents of a blob one item at a time, without
+/// building a tree.
+///
+/// This allows looking for a single node or property without allocating.
+/// After the first error, or after [`ParseEvent::End`], the iterator ends.
+///
+/// # Examples
+///
+/// ```rust
+/// use flat_device_tree::{ParseEvent, StreamingParser};
+///
+/// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+/// let parser = StreamingParser::new(buf).unwrap();
+///
+/// let nodes = parser
+///     .filter(|event| matches!(event, Ok(ParseEvent::BeginNode { .. })))
+///     .count();
+/// println!("{} nodes", nodes);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamingParser<'a> {
+    buffer: &'a [u8],
+    off_dt_strings: usize,
+    pos: usize,
+    depth: usize,
+    root_seen: bool,
+    done: bool,
+}
+
+impl<'a> StreamingParser<'a> {
+    /// Check the header of a blob and start parsing its structure block.
+    ///
+    /// The header is checked the same way as by
+    /// [`DeviceTree::load`](crate::DeviceTree::load). The structure block is
+    /// only checked as far as it is parsed.
+    pub fn new(buffer: &'a [u8]) -> Result<StreamingParser<'a>> {
+        let header = DeviceTreeHeader::load(buffer)?;
+
+        if header.totalsize as usize != buffer.len() {
+            return Err(Error::SizeMismatch);
+        }
+        if header.version != SUPPORTED_VERSION {
+            return Err(Error::VersionNotSupported);
+        }
+        header.validate()?;
+
+        Ok(StreamingParser {
+            buffer,
+            off_dt_strings: header.off_dt_strings as usize,
+            pos: header.off_dt_struct as usize,
+            depth: 0,
+            root_seen: false,
+            done: false,
+        })
+    }
+
+    /// The number of nodes that are currently open.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn parse_event(&mut self) -> Result<ParseEvent<'a>> {
+        let buffer = self.buffer;
+        let pos = skip_nops(buffer, self.pos)?;
+
+        match buffer.read_be_u32(pos)? {
+            // there is a single root node
+            OF_DT_BEGIN_NODE if self.depth > 0 || !self.root_seen => {
+                let raw_name = buffer.read_bstring0(pos + 4)?;
+
+                self.pos = align(pos + 4 + raw_name.len() + 1, 4);
+                self.depth += 1;
+                self.root_seen = true;
+                Ok(ParseEvent::BeginNode {
+                    name: str::from_utf8(raw_name)?,
+                })
+            }
+            OF_DT_PROP if self.depth > 0 => {
+                let val_size = buffer.read_be_u32(pos + 4)? as usize;
+                let name_offset = buffer.read_be_u32(pos + 8)? as usize;
+
+                let val_start = pos + 12;
+                let val_end = val_start + val_size;
+                let data = buffer.subslice(val_start, val_end)?;
+                let name =
+                    buffer.read_bstring0(self.off_dt_strings + name_offset)?;
+
+                self.pos = align(val_end, 4);
+                Ok(ParseEvent::Property {
+                    name: str::from_utf8(name)?,
+                    data,
+                })
+            }
+            OF_DT_END_NODE if self.depth > 0 => {
+                self.pos = pos + 4;
+                self.depth -= 1;
+                Ok(ParseEvent::EndNode)
+            }
+            OF_DT_END if self.depth == 0 && self.root_seen => {
+                self.pos = pos + 4;
+                Ok(ParseEvent::End)
+            }
+            _ => Err(Error::ParseError(pos)),
+        }
+    }
+}
+
+impl<'a> Iterator for StreamingParser<'a> {
+    type Item = Result<ParseEvent<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let event = self.parse_event();
+        self.done = matches!(event, Ok(ParseEvent::End) | Err(_));
+        Some(event)
+    }
+}
@@ -1,7 +1,10 @@
-use alloc::vec::Vec;
+use alloc::{string::String, vec::Vec};
+use hashbrown::HashMap;
 
+/// Strings block under construction, storing every distinct string once.
 pub struct StringTable {
     pub buffer: Vec<u8>,
+    index: HashMap<String, u32>,
 }
 
 impl Default for StringTable {
@@ -12,13 +15,20 @@ impl Default for StringTable {
 
 impl StringTable {
     pub fn new() -> StringTable {
-        StringTable { buffer: Vec::new() }
+        StringTable {
+            buffer: Vec::new(),
+            index: HashMap::new(),
+        }
     }
 
     pub fn add_string(&mut self, val: &str) -> u32 {
-        let offset = self.buffer.len();
+        if let Some(offset) = self.index.get(val) {
+            return *offset;
+        }
+        let offset = self.buffer.len() as u32;
         self.buffer.extend(val.bytes());
         self.buffer.push(0);
-        offset as u32
+        self.index.insert(val.into(), offset);
+        offset
     }
 }
@@ -8,43 +8,44 @@ pub fn align(val: usize, to: usize) -> usize {
     val + (to - (val % to)) % to
 }
 
-pub trait SliceRead {
+/// Bounds-checked reads at absolute offsets into a byte slice.
+///
+/// There is no read position; every read names its offset and leaves the
+/// slice untouched, so values can be read in any order. Slices that are
+/// read borrow from the underlying buffer for its whole lifetime `'a`.
+pub trait SliceRead<'a> {
+    fn read_be_u16(&self, pos: usize) -> SliceReadResult<u16>;
     fn read_be_u32(&self, pos: usize) -> SliceReadResult<u32>;
+    fn read_le_u32(&self, pos: usize) -> SliceReadResult<u32>;
     fn read_be_u64(&self, pos: usize) -> SliceReadResult<u64>;
-    fn read_bstring0(&self, pos: usize) -> SliceReadResult<&[u8]>;
-    fn subslice(&self, start: usize, len: usize) -> SliceReadResult<&[u8]>;
+    fn read_bstring0(&self, pos: usize) -> SliceReadResult<&'a [u8]>;
+    fn subslice(&self, start: usize, end: usize) -> SliceReadResult<&'a [u8]>;
 }
 
-impl<'a> SliceRead for &'a [u8] {
+impl<'a> SliceRead<'a> for &'a [u8] {
+    fn read_be_u16(&self, pos: usize) -> SliceReadResult<u16> {
+        let raw = self.subslice(pos, pos + 2)?;
+        Ok(u16::from_be_bytes([raw[0], raw[1]]))
+    }
+
     fn read_be_u32(&self, pos: usize) -> SliceReadResult<u32> {
-        // check size is valid
-        if pos + 4 > self.len() {
-            return Err(SliceReadError::UnexpectedEndOfInput);
-        }
+        let raw = self.subslice(pos, pos + 4)?;
+        Ok(u32::from_be_bytes([raw[0], raw[1], raw[2], raw[3]]))
+    }
 
-        Ok((self[pos] as u32) << 24
-            | (self[pos + 1] as u32) << 16
-            | (self[pos + 2] as u32) << 8
-            | (self[pos + 3] as u32))
+    fn read_le_u32(&self, pos: usize) -> SliceReadResult<u32> {
+        let raw = self.subslice(pos, pos + 4)?;
+        Ok(u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]))
     }
 
     fn read_be_u64(&self, pos: usize) -> SliceReadResult<u64> {
-        // check size is valid
-        if pos + 8 >self.len() {
-            return Err(SliceReadError::UnexpectedEndOfInput);
-        }
-
-        Ok((self[pos] as u64) << 56
-            | (self[pos + 1] as u64) << 48
-            | (self[pos + 2] as u64) << 40
-            | (self[pos + 3] as u64) << 32
-            | (self[pos + 4] as u64) << 24
-            | (self[pos + 5] as u64) << 16
-            | (self[pos + 6] as u64) << 8
-            | (self[pos + 7] as u64))
+        let raw = self.subslice(pos, pos + 8)?;
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(raw);
+        Ok(u64::from_be_bytes(bytes))
     }
 
-    fn read_bstring0(&self, pos: usize) -> SliceReadResult<&[u8]> {
+    fn read_bstring0(&self, pos: usize) -> SliceReadResult<&'a [u8]> {
         let mut cur = pos;
         while cur < self.len() {
             if self[cur] == 0 {
@@ -54,12 +55,12 @@ impl<'a> SliceRead for &'a [u8] {
             cur += 1;
         }
 
-        Err(SliceReadError::UnexpectedEndOfInput)
+        Err(SliceReadError::UnexpectedEndOfInput(pos))
     }
 
-    fn subslice(&self, start: usize, end: usize) -> SliceReadResult<&[u8]> {
-        if end >= self.len() {
-            return Err(SliceReadError::UnexpectedEndOfInput);
+    fn subslice(&self, start: usize, end: usize) -> SliceReadResult<&'a [u8]> {
+        if start > end || end > self.len() {
+            return Err(SliceReadError::UnexpectedEndOfInput(start));
         }
 
         Ok(&self[start..end])
@@ -75,7 +76,7 @@ pub trait VecWrite {
 
 impl VecWrite for Vec<u8> {
     fn write_be_u32(&mut self, pos: usize, val: u32) -> VecWriteResult {
-        if pos % 4 != 0 {
+        if !pos.is_multiple_of(4) {
             return Err(VecWriteError::UnalignedWrite);
         }
         if pos > self.len() {
@@ -95,13 +96,13 @@ impl VecWrite for Vec<u8> {
     }
 
     fn write_be_u64(&mut self, pos: usize, val: u64) -> VecWriteResult {
-        if pos % 8 != 0 {
+        if !pos.is_multiple_of(8) {
             return Err(VecWriteError::UnalignedWrite);
         }
         if pos > self.len() {
             return Err(VecWriteError::NonContiguousWrite);
         }
-        if pos > self.len() - 8 {
+        if pos + 8 > self.len() {
             for _ in 0..(pos + 8 - self.len()) {
                 self.push(0);
             }
@@ -0,0 +1,117 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::name::is_valid_node_name;
+use crate::phandle::{reference_cells, walk_phandle_list};
+use crate::{child_path, DeviceTree, Node, PhandleMap, PropError};
+
+/// A problem found by [`DeviceTree::validate`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ValidationWarning {
+    /// More than one node has this phandle.
+    DuplicatePhandle(u32),
+
+    /// This phandle is referred to, but no node has it.
+    MissingPhandle(u32),
+
+    /// The length of the `reg` property of the node at `path` is not a
+    /// multiple of the entry size given by its parent's `#address-cells`
+    /// and `#size-cells`.
+    RegSizeMismatch { path: String },
+
+    /// The node at this path has a name that is not allowed by the
+    /// specification.
+    InvalidNodeName(String),
+}
+
+impl Node {
+    fn lint(
+        &self,
+        path: &str,
+        parent: Option<&Node>,
+        phandles: &PhandleMap,
+        warnings: &mut Vec<ValidationWarning>,
+    ) {
+        if parent.is_some() && !is_valid_node_name(&self.name) {
+            warnings.push(ValidationWarning::InvalidNodeName(path.into()));
+        }
+
+        if let (Some(parent), Some(reg)) = (parent, self.property("reg")) {
+            let address_cells = parent.address_cells();
+            let size_cells = parent.size_cells();
+            if let (Some(address_cells), Some(size_cells)) =
+                (address_cells, size_cells)
+            {
+                // counts this large cannot describe any value
+                let entry_len = (address_cells as usize)
+                    .checked_add(size_cells as usize)
+                    .and_then(|cells| cells.checked_mul(4));
+                if entry_len.is_none_or(|len| {
+                    len > 0 && !reg.data().len().is_multiple_of(len)
+                }) {
+                    warnings.push(ValidationWarning::RegSizeMismatch {
+                        path: path.into(),
+                    });
+                }
+            }
+        }
+
+        // the properties of these nodes are paths, whatever their names
+        let paths_only = matches!(path, "/aliases" | "/__symbols__");
+
+        for prop in self.props.iter().filter(|_| !paths_only) {
+            let Some(cells_name) = reference_cells(&prop.name) else {
+                continue;
+            };
+            if !prop.is_cell_aligned() {
+                continue;
+            }
+
+            let cells: Vec<u32> = prop.cells().collect();
+            let walk = walk_phandle_list(
+                &cells,
+                cells_name,
+                |phandle| phandles.get(phandle),
+                |_, _, _, _| {},
+            );
+            if let Err(PropError::UnknownPhandle(phandle)) = walk {
+                let warning = ValidationWarning::MissingPhandle(phandle);
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+        }
+
+        for child in self.children.iter() {
+            let path = child_path(path, &child.name);
+            child.lint(&path, Some(self), phandles, warnings);
+        }
+    }
+}
+
+impl DeviceTree {
+    /// Check the tree for problems that do not stop it from being loaded,
+    /// but that other software may trip over.
+    ///
+    /// This looks for duplicate phandles, references to phandles that no
+    /// node has, `reg` properties that do not fit the cell counts of their
+    /// node's parent and invalid node names. References are found in the
+    /// properties [`phandle_references`](DeviceTree::phandle_references)
+    /// decodes exactly. Each phandle is reported at most once per kind of
+    /// problem.
+    ///
+    /// An empty list means no problems were found.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let phandles = self.phandle_map();
+        let mut warnings = Vec::new();
+
+        for &phandle in phandles.duplicates() {
+            let warning = ValidationWarning::DuplicatePhandle(phandle);
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+
+        self.root.lint("/", None, &phandles, &mut warnings);
+        warnings
+    }
+}
@@ -0,0 +1,218 @@
+//! Visitor-based traversal of a tree.
+//!
+//! Unlike the iterators in [`iter`](crate::iter), visiting a tree with a
+//! [`Visitor`] does not allocate.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use flat_device_tree::visit::{VisitAction, Visitor};
+//! use flat_device_tree::{DeviceTree, Node};
+//!
+//! struct NodeCounter(usize);
+//!
+//! impl Visitor for NodeCounter {
+//!     fn visit_node(&mut self, _node: &Node, _depth: usize) -> VisitAction {
+//!         self.0 += 1;
+//!         VisitAction::Continue
+//!     }
+//! }
+//!
+//! let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+//! let dt = DeviceTree::load(buf).unwrap();
+//!
+//! let mut counter = NodeCounter(0);
+//! dt.visit(&mut counter);
+//! println!("{} nodes", counter.0);
+//! ```
+
+use alloc::vec;
+use core::ops::ControlFlow;
+
+use crate::{DeviceTree, Node, Property};
+
+/// What to do after visiting a node.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VisitAction {
+    /// Visit the properties and children of the node.
+    Continue,
+
+    /// Visit the properties of the node, but not its children.
+    SkipChildren,
+
+    /// End the traversal.
+    Stop,
+}
+
+/// What to do after a node was passed to the callback of [`Node::walk`].
+///
+/// Ending the walk altogether is done by returning
+/// [`ControlFlow::Break`] instead.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WalkAction {
+    /// Walk the children of the node.
+    Continue,
+
+    /// Skip the children of the node.
+    SkipChildren,
+}
+
+/// Callbacks for a depth-first traversal of a tree.
+///
+/// Each node is visited before its properties, which are visited before its
+/// children.
+pub trait Visitor {
+    /// Called for every node, with the root at depth `0`.
+    fn visit_node(&mut self, node: &Node, depth: usize) -> VisitAction {
+        let _ = (node, depth);
+        VisitAction::Continue
+    }
+
+    /// Called for every property of a visited node.
+    fn visit_property(&mut self, prop: &Property) {
+        let _ = prop;
+    }
+}
+
+impl Node {
+    /// Visit this node and its descendants. Returns `false` if the
+    /// traversal was stopped.
+    fn visit<V: Visitor + ?Sized>(
+        &self,
+        visitor: &mut V,
+        depth: usize,
+    ) -> bool {
+        let action = visitor.visit_node(self, depth);
+        if action == VisitAction::Stop {
+            return false;
+        }
+
+        for prop in self.props.iter() {
+            visitor.visit_property(prop);
+        }
+
+        if action == VisitAction::SkipChildren {
+            return true;
+        }
+
+        self.children
+            .iter()
+            .all(|child| child.visit(visitor, depth + 1))
+    }
+}
+
+impl Node {
+    /// Walk this node and its descendants depth-first, calling `f` with
+    /// every node and its depth below this node, which has depth `0`.
+    ///
+    /// `f` decides whether to descend into the children of a node, or to end
+    /// the walk by returning [`ControlFlow::Break`], which is then returned.
+    /// Like the iterators in [`iter`](crate::iter), this keeps an explicit
+    /// stack instead of recursing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::ops::ControlFlow;
+    /// use flat_device_tree::visit::WalkAction;
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// // find the first uart, without looking into disabled nodes
+    /// let mut uart = None;
+    /// dt.root.walk(|node, _| {
+    ///     if !node.is_enabled() {
+    ///         return ControlFlow::Continue(WalkAction::SkipChildren);
+    ///     }
+    ///     if node.base_name() == "uart" {
+    ///         uart = Some(node);
+    ///         return ControlFlow::Break(());
+    ///     }
+    ///     ControlFlow::Continue(WalkAction::Continue)
+    /// });
+    ///
+    /// assert_eq!(uart.unwrap().name(), "uart@7e201000");
+    /// ```
+    pub fn walk<'a, F>(&'a self, mut f: F) -> ControlFlow<()>
+    where
+        F: FnMut(&'a Node, usize) -> ControlFlow<(), WalkAction>,
+    {
+        if f(self, 0)? == WalkAction::SkipChildren {
+            return ControlFlow::Continue(());
+        }
+
+        // the remaining children of every node on the path to the current one
+        let mut stack = vec![self.children.iter()];
+        while let Some(siblings) = stack.last_mut() {
+            let Some(node) = siblings.next() else {
+                stack.pop();
+                continue;
+            };
+
+            if f(node, stack.len())? == WalkAction::Continue {
+                stack.push(node.children.iter());
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Walk this node and its descendants like [`walk`](Node::walk), but
+    /// with mutable access to every node.
+    ///
+    /// `f` may change anything about the node it is given, including its
+    /// children: they are walked only after `f` returns, so any children it
+    /// adds are walked and any it removes are not. The parents and siblings
+    /// of the node are out of reach.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use core::ops::ControlFlow;
+    /// use flat_device_tree::visit::WalkAction;
+    /// use flat_device_tree::DeviceTree;
+    ///
+    /// let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    /// let mut dt = DeviceTree::load(buf).unwrap();
+    ///
+    /// // disable every i2c controller
+    /// let _ = dt.root.walk_mut(|node, _| {
+    ///     if node.base_name() == "i2c" {
+    ///         node.set_property("status", b"disabled\0".to_vec()).unwrap();
+    ///     }
+    ///     ControlFlow::Continue(WalkAction::Continue)
+    /// });
+    /// ```
+    pub fn walk_mut<F>(&mut self, mut f: F) -> ControlFlow<()>
+    where
+        F: FnMut(&mut Node, usize) -> ControlFlow<(), WalkAction>,
+    {
+        if f(self, 0)? == WalkAction::SkipChildren {
+            return ControlFlow::Continue(());
+        }
+
+        let mut stack = vec![self.children.iter_mut()];
+        while let Some(siblings) = stack.last_mut() {
+            let Some(node) = siblings.next() else {
+                stack.pop();
+                continue;
+            };
+
+            if f(node, stack.len())? == WalkAction::Continue {
+                stack.push(node.children.iter_mut());
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+impl DeviceTree {
+    /// Walk the tree depth-first, calling `visitor` for every node and
+    /// property.
+    pub fn visit<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        self.root.visit(visitor, 0);
+    }
+}
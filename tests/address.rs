@@ -0,0 +1,388 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+fn entries(node: &Node, parent: &Node) -> Option<Vec<RegEntry>> {
+    node.reg(parent).map(Iterator::collect)
+}
+
+#[test]
+fn reg_single_cells() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let soc = dt.find("/soc").unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+
+    assert_eq!(
+        entries(uart, soc),
+        Some(vec![RegEntry {
+            address: 0x7e20_1000,
+            size: 0x1000,
+        }])
+    );
+}
+
+#[test]
+fn reg_without_size() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let clocks = dt.find("/clocks").unwrap();
+    let clock = dt.find("/clocks/clock@1").unwrap();
+
+    assert_eq!(
+        entries(clock, clocks),
+        Some(vec![RegEntry {
+            address: 1,
+            size: 0
+        }])
+    );
+}
+
+#[test]
+fn reg_64bit_memory() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 2)
+        .property_u32("#size-cells", 2);
+    root.child("memory@80000000").property(
+        "reg",
+        &[
+            0, 0, 0, 0, 0x80, 0, 0, 0, // address
+            0, 0, 0, 1, 0, 0, 0, 0, // size
+            0, 0, 0, 8, 0x80, 0, 0, 0, // address
+            0, 0, 0, 0, 0x40, 0, 0, 0, // size
+        ],
+    );
+    let dt = builder.build().unwrap();
+    let memory = dt.find("/memory@80000000").unwrap();
+
+    assert_eq!(
+        entries(memory, &dt.root),
+        Some(vec![
+            RegEntry {
+                address: 0x8000_0000,
+                size: 0x1_0000_0000,
+            },
+            RegEntry {
+                address: 0x8_8000_0000,
+                size: 0x4000_0000,
+            },
+        ])
+    );
+}
+
+#[test]
+fn reg_default_cells() {
+    let mut builder = DeviceTreeBuilder::new();
+    // 2 address cells and 1 size cell
+    builder
+        .root("")
+        .child("dev@1")
+        .property("reg", &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3]);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(
+        entries(dt.find("/dev@1").unwrap(), &dt.root),
+        Some(vec![RegEntry {
+            address: 0x1_0000_0002,
+            size: 3,
+        }])
+    );
+}
+
+#[test]
+fn reg_invalid() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1);
+    root.child("short")
+        .property("reg", &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0]);
+    root.child("odd")
+        .property("reg", &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3]);
+    root.child("none");
+    let dt = builder.build().unwrap();
+
+    for name in ["short", "odd", "none"] {
+        let node = dt.root.find_child(name).unwrap();
+        assert!(node.reg(&dt.root).is_none(), "{}", name);
+    }
+
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 3);
+    root.child("wide").property("reg", &[0; 16]);
+    let dt = builder.build().unwrap();
+
+    assert!(dt.find("/wide").unwrap().reg(&dt.root).is_none());
+}
+
+#[test]
+fn reg_raw_and_cells() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+
+    assert_eq!(
+        uart.reg_raw(),
+        Some(&[0x7e, 0x20, 0x10, 0, 0, 0, 0x10, 0][..])
+    );
+    assert_eq!(uart.reg_cells(1, 1), Some(vec![(0x7e20_1000, 0x1000)]));
+    assert_eq!(uart.reg_cells(2, 0), Some(vec![(0x7e20_1000_0000_1000, 0)]));
+    assert_eq!(uart.reg_cells(1, 2), None);
+    assert_eq!(dt.root.reg_raw(), None);
+    assert_eq!(dt.root.reg_cells(1, 1), None);
+}
+
+#[test]
+fn reg_for_node_uses_parent_cells() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(
+        dt.reg_for_node("/soc/uart@7e201000"),
+        Some(vec![(0x7e20_1000, 0x1000)])
+    );
+    // /clocks has a #size-cells of 0
+    assert_eq!(dt.reg_for_node("/clocks/clock@1"), Some(vec![(1, 0)]));
+    assert_eq!(dt.reg_for_node("/soc"), None);
+    assert_eq!(dt.reg_for_node("/soc/uart@7e000000"), None);
+}
+
+#[test]
+fn ranges_entries() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let soc = dt.find("/soc").unwrap();
+
+    assert_eq!(
+        soc.ranges(&dt.root).map(Iterator::collect::<Vec<_>>),
+        Some(vec![AddressRange {
+            child_address: 0x7e00_0000,
+            parent_address: 0x3f00_0000,
+            size: 0x100_0000,
+        }])
+    );
+    assert!(dt.root.ranges(&dt.root).is_none());
+}
+
+#[test]
+fn ranges_with_explicit_cells() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let soc = dt.find("/soc").unwrap();
+
+    assert_eq!(
+        soc.ranges_cells(1, 1, 1),
+        Some(vec![AddressRange {
+            child_address: 0x7e00_0000,
+            parent_address: 0x3f00_0000,
+            size: 0x100_0000,
+        }])
+    );
+    assert_eq!(
+        soc.ranges_cells(1, 2, 0),
+        Some(vec![AddressRange {
+            child_address: 0x7e00_0000,
+            parent_address: 0x3f00_0000_0100_0000,
+            size: 0,
+        }])
+    );
+    assert_eq!(soc.ranges_cells(2, 2, 1), None);
+    assert_eq!(soc.ranges_cells(3, 0, 0), None);
+    assert_eq!(dt.root.ranges_cells(1, 1, 1), None);
+}
+
+#[test]
+fn translate_soc_address() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(
+        dt.translate_address("/soc/uart@7e201000", 0x7e20_1000),
+        Some(0x3f20_1000)
+    );
+    // outside of the window of /soc
+    assert_eq!(
+        dt.translate_address("/soc/uart@7e201000", 0x7f00_0000),
+        None
+    );
+    // children of the root are already in the CPU address space
+    assert_eq!(dt.translate_address("/memory", 0x1000), Some(0x1000));
+    assert_eq!(dt.translate_address("/soc/nonexistent", 0x7e20_1000), None);
+}
+
+/// A tree with two nested buses, using 2, 1 and 1 address cells from the
+/// root down.
+fn nested_buses(inner_ranges: Option<&[u8]>) -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 2)
+        .property_u32("#size-cells", 2);
+
+    let mut outer = root.child("outer");
+    outer
+        .property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1)
+        // 0x0 -> 0x1_0000_0000, size 0x1000_0000
+        .property(
+            "ranges",
+            &[0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0x10, 0, 0, 0],
+        );
+
+    let mut inner = outer.child("inner@100000");
+    inner
+        .property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1);
+    if let Some(ranges) = inner_ranges {
+        inner.property("ranges", ranges);
+    }
+    inner.child("dev@0");
+
+    builder.build().unwrap()
+}
+
+#[test]
+fn translate_nested_buses() {
+    // 0x0 -> 0x10_0000, size 0x1000
+    let dt = nested_buses(Some(&[0, 0, 0, 0, 0, 0x10, 0, 0, 0, 0, 0x10, 0]));
+
+    assert_eq!(
+        dt.translate_address("/outer/inner@100000/dev@0", 0x20),
+        Some(0x1_0010_0020)
+    );
+    assert_eq!(
+        dt.translate_address("/outer/inner@100000/dev@0", 0x1000),
+        None
+    );
+}
+
+#[test]
+fn translate_identity_ranges() {
+    let dt = nested_buses(Some(&[]));
+
+    assert_eq!(
+        dt.translate_address("/outer/inner@100000/dev@0", 0x20),
+        Some(0x1_0000_0020)
+    );
+}
+
+#[test]
+fn translate_without_ranges() {
+    let dt = nested_buses(None);
+
+    assert_eq!(
+        dt.translate_address("/outer/inner@100000/dev@0", 0x20),
+        None
+    );
+    assert_eq!(
+        dt.translate_address("/outer/inner@100000", 0x20),
+        Some(0x1_0000_0020)
+    );
+}
+
+/// A 32-bit bus below a 64-bit root, whose devices see the RAM at
+/// 0x8000_0000 at address 0.
+fn dma_bus(dma_ranges: Option<&[u8]>) -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 2)
+        .property_u32("#size-cells", 2);
+
+    let mut bus = root.child("bus");
+    bus.property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1)
+        .property("ranges", &[]);
+    if let Some(dma_ranges) = dma_ranges {
+        bus.property("dma-ranges", dma_ranges);
+    }
+    bus.child("dma@1000");
+
+    builder.build().unwrap()
+}
+
+#[test]
+fn dma_ranges_entries() {
+    let dt = dma_bus(Some(&[
+        0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0, 0, 0, 0x40, 0, 0, 0,
+    ]));
+    let bus = dt.find("/bus").unwrap();
+
+    assert_eq!(
+        bus.dma_ranges(&dt.root).map(Iterator::collect::<Vec<_>>),
+        Some(vec![AddressRange {
+            child_address: 0,
+            parent_address: 0x8000_0000,
+            size: 0x4000_0000,
+        }])
+    );
+    assert!(dt.root.dma_ranges(&dt.root).is_none());
+}
+
+#[test]
+fn translate_dma() {
+    let dt = dma_bus(Some(&[
+        0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0, 0, 0, 0x40, 0, 0, 0,
+    ]));
+
+    assert_eq!(
+        dt.translate_dma_address("/bus/dma@1000", 0x1000),
+        Some(0x8000_1000)
+    );
+    assert_eq!(dt.translate_dma_address("/bus/dma@1000", 0x4000_0000), None);
+    // MMIO addresses go through ranges instead
+    assert_eq!(dt.translate_address("/bus/dma@1000", 0x1000), Some(0x1000));
+}
+
+#[test]
+fn translate_dma_identity() {
+    for dt in [dma_bus(Some(&[])), dma_bus(None)] {
+        assert_eq!(
+            dt.translate_dma_address("/bus/dma@1000", 0x1000),
+            Some(0x1000)
+        );
+    }
+}
+
+#[test]
+fn effective_cell_counts() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    // the root describes its own address space
+    assert_eq!(dt.address_cells("/"), Some(1));
+    assert_eq!(dt.size_cells("/"), Some(1));
+    // a child of the root
+    assert_eq!(dt.address_cells("/soc"), Some(1));
+    // nodes on a simple-bus use the cell counts of the bus
+    assert_eq!(dt.address_cells("/soc/uart@7e201000"), Some(1));
+    assert_eq!(dt.size_cells("/soc/uart@7e201000"), Some(1));
+    assert_eq!(dt.address_cells("/clocks/clock@0"), Some(1));
+    assert_eq!(dt.size_cells("/clocks/clock@0"), Some(0));
+
+    assert_eq!(dt.address_cells("/nonexistent"), None);
+}
+
+#[test]
+fn default_cell_counts() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 1);
+    let mut bus = root.child("bus");
+    bus.child("dev@0");
+    bus.property_u32("#size-cells", 0);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.address_cells("/"), Some(1));
+    assert_eq!(dt.size_cells("/"), Some(1));
+    assert_eq!(dt.address_cells("/bus/dev@0"), Some(2));
+    assert_eq!(dt.size_cells("/bus/dev@0"), Some(0));
+}
+
+#[test]
+fn malformed_cell_counts() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property("#address-cells", &[0, 0, 0, 0, 0, 0, 0, 1]);
+    root.child("dev@0")
+        .property("reg", &[0, 0, 0, 1, 0, 0, 0, 1]);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.address_cells("/dev@0"), None);
+    assert_eq!(dt.size_cells("/dev@0"), Some(1));
+    assert!(dt.find("/dev@0").unwrap().reg(&dt.root).is_none());
+}
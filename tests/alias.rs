@@ -0,0 +1,113 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+fn board() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("aliases")
+        .property_str("serial0", "/soc/uart@10000")
+        .property_str("ethernet0", "/soc/ethernet@20000")
+        .property_str("stale", "/soc/missing")
+        .property_u32("bogus", 1);
+
+    let mut soc = root.child("soc");
+    soc.child("uart@10000");
+    soc.child("ethernet@20000");
+
+    builder.build().unwrap()
+}
+
+#[test]
+fn resolve_aliases() {
+    let dt = board();
+
+    assert_eq!(dt.resolve_alias("serial0"), Some("/soc/uart@10000"));
+    assert_eq!(dt.resolve_alias("ethernet0"), Some("/soc/ethernet@20000"));
+    // the path is returned as stored, even if there is no such node
+    assert_eq!(dt.resolve_alias("stale"), Some("/soc/missing"));
+    assert!(dt.resolve_alias("bogus").is_none());
+    assert!(dt.resolve_alias("serial1").is_none());
+
+    assert_eq!(dt.find_node("serial0").unwrap().name(), "uart@10000");
+    assert!(dt.find_node("stale").is_none());
+    assert!(dt.find_node("bogus").is_none());
+}
+
+#[test]
+fn list_aliases() {
+    let dt = board();
+
+    assert_eq!(
+        dt.aliases().collect::<Vec<_>>(),
+        vec![
+            ("serial0", "/soc/uart@10000"),
+            ("ethernet0", "/soc/ethernet@20000"),
+            ("stale", "/soc/missing"),
+        ]
+    );
+}
+
+#[test]
+fn no_aliases_node() {
+    let dt = DeviceTreeBuilder::new().build().unwrap();
+
+    assert!(dt.resolve_alias("serial0").is_none());
+    assert_eq!(dt.aliases().count(), 0);
+}
+
+#[test]
+fn rpi_aliases() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let uart = dt.find_node("uart0").unwrap();
+    assert_eq!(uart.property_u32("phandle"), Some(0x17));
+    assert_eq!(dt.aliases().count(), 24);
+    assert!(dt.aliases().any(|alias| alias == ("soc", "/soc")));
+}
+
+#[test]
+fn find_node_through_aliases() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+
+    assert!(std::ptr::eq(dt.find_node("uart0").unwrap(), uart));
+    assert!(std::ptr::eq(dt.find_node("uart0:115200n8").unwrap(), uart));
+    assert!(std::ptr::eq(
+        dt.find_node("soc/uart@7e201000").unwrap(),
+        uart
+    ));
+    assert!(std::ptr::eq(
+        dt.find_node("/soc/uart@7e201000:115200").unwrap(),
+        uart
+    ));
+    assert_eq!(dt.find_node("spi0/spidev@0").unwrap().name(), "spidev@0");
+
+    assert!(dt.find_node("serial7").is_none());
+    assert!(dt.find_node("uart0/missing").is_none());
+    assert!(dt.find_node("").is_none());
+}
+
+#[test]
+fn find_node_mut_through_aliases() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+
+    let uart = dt.find_node_mut("uart0:115200n8").unwrap();
+    uart.set_property("status", b"disabled\0".to_vec()).unwrap();
+    assert!(!dt.find("/soc/uart@7e201000").unwrap().is_enabled());
+
+    let spidev = dt.find_node_mut("spi0/spidev@0").unwrap();
+    assert_eq!(spidev.name(), "spidev@0");
+    assert_eq!(dt.find_node_mut("/soc/").unwrap().name(), "soc");
+    assert!(dt.find_node_mut("/").unwrap().name().is_empty());
+
+    // both variants accept the same paths
+    for path in ["uart0", "soc/uart@7e201000", "serial7", "uart0/missing", ""] {
+        let found = dt.find_node(path).map(|node| node.name().to_owned());
+        let found_mut =
+            dt.find_node_mut(path).map(|node| node.name().to_owned());
+        assert_eq!(found, found_mut, "{}", path);
+    }
+}
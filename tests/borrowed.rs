@@ -0,0 +1,56 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn borrows_from_blob() {
+    let dt = BorrowedTree::load(DTB).unwrap();
+    let range = DTB.as_ptr_range();
+
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(uart.name, "uart@7e201000");
+    assert!(range.contains(&uart.name.as_ptr()));
+
+    let status = uart.property("status").unwrap();
+    assert_eq!(status.as_str(), Some("okay"));
+    assert!(range.contains(&status.data.as_ptr()));
+    assert!(range.contains(&status.name.as_ptr()));
+
+    let cpus = dt.root().find_child("cpus").unwrap();
+    assert_eq!(cpus.property("#address-cells").unwrap().as_u32(), Some(1));
+    assert_eq!(cpus.find("cpu@2").unwrap().name, "cpu@2");
+    assert!(dt.find("/soc/uart").is_none());
+    assert!(dt.find("soc").is_none());
+}
+
+#[test]
+fn matches_owned_tree() {
+    let owned = DeviceTree::load(DTB).unwrap();
+    let borrowed = BorrowedTree::load(DTB).unwrap();
+
+    assert_eq!(borrowed.to_tree(), owned);
+    assert_eq!(borrowed.header(), owned.header());
+    assert_eq!(borrowed.version, owned.version);
+    assert_eq!(borrowed.reserved, owned.reserved);
+
+    let soc = borrowed.find("/soc").unwrap();
+    assert_eq!(&soc.to_node(), owned.find("/soc").unwrap());
+    assert_eq!(
+        soc.props[0].to_property(),
+        owned.find("/soc").unwrap().properties()[0]
+    );
+}
+
+#[test]
+fn load_errors() {
+    assert_eq!(
+        BorrowedTree::load(&DTB[..DTB.len() - 4]).unwrap_err(),
+        Error::SizeMismatch
+    );
+    assert_eq!(
+        BorrowedTree::load(&[0; 40]).unwrap_err(),
+        Error::InvalidMagicNumber
+    );
+}
@@ -0,0 +1,103 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+#[test]
+fn build_tree() {
+    let mut builder = DeviceTreeBuilder::new();
+    builder
+        .memory_reservation(0x1000_0000, 0x1000)
+        .memory_reservation(0x2000_0000, 0x2000);
+
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 1)
+        .property_str("model", "test board");
+    let mut soc = root.child("soc");
+    soc.property("ranges", &[]);
+    soc.child("uart@1000").property_str("status", "okay");
+    root.child("chosen");
+
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.root.property_u32("#address-cells"), Some(1));
+    assert_eq!(dt.root.property_str("model"), Some("test board"));
+    assert!(dt.find("/soc").unwrap().has_flag("ranges"));
+    assert_eq!(
+        dt.find("/soc/uart@1000").unwrap().property_str("status"),
+        Some("okay")
+    );
+    assert!(dt.find("/chosen").is_some());
+    assert_eq!(
+        dt.memory_reservations(),
+        &[
+            MemoryReservation {
+                address: 0x1000_0000,
+                size: 0x1000
+            },
+            MemoryReservation {
+                address: 0x2000_0000,
+                size: 0x2000
+            },
+        ]
+    );
+
+    let loaded = DeviceTree::load(&dt.store().unwrap()).unwrap();
+    assert_eq!(loaded, dt);
+}
+
+#[test]
+fn build_empty_tree() {
+    let dt = DeviceTreeBuilder::new().build().unwrap();
+
+    assert_eq!(dt.root.name(), "");
+    assert!(dt.root.properties().is_empty());
+    assert!(dt.root.children().is_empty());
+}
+
+#[test]
+fn reject_invalid_node_names() {
+    for name in ["", "has space", "uart@", "@1000", "a@b@c", "näme"] {
+        let mut builder = DeviceTreeBuilder::new();
+        builder.root("").child("soc").child(name);
+
+        assert_eq!(
+            builder.build(),
+            Err(Error::NodeError(NodeError::InvalidNodeName)),
+            "{:?}",
+            name
+        );
+    }
+
+    let mut builder = DeviceTreeBuilder::new();
+    builder.root("not valid");
+    assert_eq!(
+        builder.build(),
+        Err(Error::NodeError(NodeError::InvalidNodeName))
+    );
+}
+
+#[test]
+fn accept_valid_node_names() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    for name in ["cpus", "cpu@0", "__symbols__", "i2c@7e804000", "a,b.c+d-e"] {
+        root.child(name);
+    }
+
+    assert!(builder.build().is_ok());
+}
+
+#[test]
+fn reject_invalid_property_names() {
+    for name in ["", "has space", "tab\t", "ünicode"] {
+        let mut builder = DeviceTreeBuilder::new();
+        builder.root("").child("soc").property(name, &[]);
+
+        assert_eq!(
+            builder.build(),
+            Err(Error::NodeError(NodeError::InvalidPropertyName)),
+            "{:?}",
+            name
+        );
+    }
+}
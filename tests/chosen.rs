@@ -0,0 +1,168 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn chosen_properties() {
+    let mut builder = DeviceTreeBuilder::new();
+    builder
+        .root("")
+        .child("chosen")
+        .property_str("bootargs", "console=ttyS0 root=/dev/vda")
+        .property_str("stdout-path", "serial0:115200n8")
+        .property_u32("linux,initrd-start", 0x4800_0000)
+        .property("linux,initrd-end", &[0, 0, 0, 1, 0x48, 0x20, 0, 0]);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.bootargs(), Some("console=ttyS0 root=/dev/vda"));
+    assert_eq!(dt.stdout_path(), Some("serial0:115200n8"));
+    assert_eq!(dt.initrd_start(), Some(0x4800_0000));
+    assert_eq!(dt.initrd_end(), Some(0x1_4820_0000));
+}
+
+#[test]
+fn legacy_stdout_path() {
+    let mut builder = DeviceTreeBuilder::new();
+    builder
+        .root("")
+        .child("chosen")
+        .property_str("linux,stdout-path", "/soc/uart@10000");
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.stdout_path(), Some("/soc/uart@10000"));
+}
+
+#[test]
+fn missing_chosen() {
+    let dt = DeviceTreeBuilder::new().build().unwrap();
+
+    assert_eq!(dt.bootargs(), None);
+    assert_eq!(dt.stdout_path(), None);
+    assert_eq!(dt.initrd_start(), None);
+    assert_eq!(dt.initrd_end(), None);
+}
+
+#[test]
+fn rpi_chosen() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    // the firmware fills in the command line at boot
+    assert_eq!(dt.bootargs(), Some(""));
+    assert_eq!(dt.stdout_path(), None);
+    assert_eq!(dt.initrd_start(), None);
+}
+
+fn console(stdout_path: &str) -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("aliases")
+        .property_str("serial0", "/soc/uart@10000");
+    root.child("chosen")
+        .property_str("stdout-path", stdout_path);
+    root.child("soc").child("uart@10000");
+    builder.build().unwrap()
+}
+
+#[test]
+fn stdout_node_through_alias() {
+    let dt = console("serial0:115200n8");
+    let chosen = dt.chosen().unwrap();
+
+    assert_eq!(chosen.stdout_path(), Some("serial0:115200n8"));
+    assert_eq!(chosen.stdout_node().unwrap().name(), "uart@10000");
+    assert_eq!(
+        chosen.stdout_options(),
+        Some(SerialOptions {
+            baud: 115200,
+            parity: Parity::None,
+            data_bits: 8,
+            flow_control: false,
+        })
+    );
+    assert_eq!(chosen.node().name(), "chosen");
+}
+
+#[test]
+fn stdout_node_by_path() {
+    let dt = console("/soc/uart@10000");
+    let chosen = dt.chosen().unwrap();
+
+    assert_eq!(chosen.stdout_node().unwrap().name(), "uart@10000");
+    assert_eq!(chosen.stdout_options(), None);
+
+    let dt = console("serial1:9600");
+    assert!(dt.chosen().unwrap().stdout_node().is_none());
+}
+
+#[test]
+fn parse_serial_options() {
+    let parse = SerialOptions::parse;
+
+    assert_eq!(
+        parse("9600e7r"),
+        Some(SerialOptions {
+            baud: 9600,
+            parity: Parity::Even,
+            data_bits: 7,
+            flow_control: true,
+        })
+    );
+    assert_eq!(parse("57600o").unwrap().parity, Parity::Odd);
+    assert_eq!(parse("57600o").unwrap().data_bits, 8);
+    assert_eq!(parse("1500000").unwrap().baud, 1_500_000);
+    assert_eq!(parse(""), None);
+    assert_eq!(parse("n8"), None);
+}
+
+#[test]
+fn chosen_accessors() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let chosen = dt.chosen().unwrap();
+
+    assert_eq!(chosen.bootargs(), Some(""));
+    assert_eq!(chosen.stdout_path(), None);
+    assert!(chosen.stdout_node().is_none());
+    assert_eq!(chosen.initrd_start(), None);
+
+    assert!(DeviceTreeBuilder::new().build().unwrap().chosen().is_none());
+}
+
+#[test]
+fn initrd_range() {
+    let chosen = |start: &[u8], end: &[u8]| {
+        let mut builder = DeviceTreeBuilder::new();
+        let mut root = builder.root("");
+        let mut chosen = root.child("chosen");
+        if !start.is_empty() {
+            chosen.property("linux,initrd-start", start);
+        }
+        if !end.is_empty() {
+            chosen.property("linux,initrd-end", end);
+        }
+        builder.build().unwrap()
+    };
+
+    // 64-bit start, 32-bit end
+    let dt = chosen(&[0, 0, 0, 0, 0x48, 0, 0, 0], &[0x48, 0x20, 0, 0]);
+    assert_eq!(
+        dt.chosen().unwrap().initrd(),
+        Some((0x4800_0000, 0x4820_0000))
+    );
+
+    let dt = chosen(&[0x48, 0, 0, 0], &[0, 0, 0, 1, 0, 0, 0, 0]);
+    assert_eq!(
+        dt.chosen().unwrap().initrd(),
+        Some((0x4800_0000, 0x1_0000_0000))
+    );
+
+    let dt = chosen(&[0x48, 0, 0, 0], &[]);
+    assert_eq!(dt.chosen().unwrap().initrd(), None);
+    let dt = chosen(&[], &[0x48, 0, 0, 0]);
+    assert_eq!(dt.chosen().unwrap().initrd(), None);
+
+    // neither 32 nor 64 bits
+    let dt = chosen(&[0x48, 0, 0, 0], &[0x48, 0x20, 0]);
+    assert_eq!(dt.chosen().unwrap().initrd(), None);
+}
@@ -0,0 +1,96 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn rpi_clocks() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+
+    let clocks = uart.clocks(&dt).unwrap();
+    assert_eq!(clocks.len(), 2);
+    assert_eq!(clocks[0].provider.name(), "clock@2");
+    assert_eq!(clocks[0].name, Some("uartclk"));
+    assert_eq!(clocks[0].frequency(), Some(3_000_000));
+    assert_eq!(clocks[1].name, Some("apb_pclk"));
+    assert_eq!(clocks[1].frequency(), Some(126_000_000));
+
+    let apb = uart.clock_by_name(&dt, "apb_pclk").unwrap().unwrap();
+    assert_eq!(apb.provider.name(), "clock@3");
+    assert!(uart.clock_by_name(&dt, "baudclk").unwrap().is_none());
+
+    // the cprman is not a fixed clock
+    let sdhost = dt.find("/soc/sdhost@7e202000").unwrap();
+    let clocks = sdhost.clocks(&dt).unwrap();
+    assert_eq!(clocks[0].provider.name(), "clock@0");
+    assert_eq!(clocks[0].name, None);
+}
+
+fn board() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("osc")
+        .property_str("compatible", "fixed-clock")
+        .property_u32("#clock-cells", 0)
+        .property("clock-frequency", &[0, 0, 0, 1, 0, 0, 0, 0])
+        .property_u32("phandle", 1);
+    root.child("pll")
+        .property_str("compatible", "vendor,pll")
+        .property_u32("#clock-cells", 1)
+        .property_u32("clock-frequency", 800_000_000)
+        .property_u32("phandle", 2);
+    // <&pll 5>, <&osc>, <&pll 6> with only two names
+    root.child("uart")
+        .property(
+            "clocks",
+            &[0, 0, 0, 2, 0, 0, 0, 5, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 6],
+        )
+        .property("clock-names", b"baudclk\0osc\0")
+        .property_u32("clock-frequency", 48_000_000);
+    root.child("gpio");
+    builder.build().unwrap()
+}
+
+#[test]
+fn clock_names() {
+    let dt = board();
+    let uart = dt.find("/uart").unwrap();
+
+    let clocks = uart.clocks(&dt).unwrap();
+    let summary: Vec<_> = clocks
+        .iter()
+        .map(|clock| (clock.provider.name(), clock.args.clone(), clock.name))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("pll", vec![5], Some("baudclk")),
+            ("osc", vec![], Some("osc")),
+            ("pll", vec![6], None),
+        ]
+    );
+
+    let baud = uart.clock_by_name(&dt, "baudclk").unwrap().unwrap();
+    assert_eq!(baud.args, [5]);
+    // only fixed clocks have a known rate
+    assert_eq!(baud.frequency(), None);
+    assert_eq!(clocks[1].frequency(), Some(0x1_0000_0000));
+}
+
+#[test]
+fn clock_frequency() {
+    let dt = board();
+
+    assert_eq!(
+        dt.find("/uart").unwrap().clock_frequency(),
+        Some(48_000_000)
+    );
+    assert_eq!(
+        dt.find("/osc").unwrap().clock_frequency(),
+        Some(0x1_0000_0000)
+    );
+    assert_eq!(dt.find("/gpio").unwrap().clock_frequency(), None);
+    assert_eq!(dt.find("/gpio").unwrap().clocks(&dt), Ok(Vec::new()));
+}
@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+
+pub const BEGIN_NODE: u32 = 0x1;
+pub const END_NODE: u32 = 0x2;
+pub const PROP: u32 = 0x3;
+pub const NOP: u32 = 0x4;
+pub const END: u32 = 0x9;
+
+/// Hand-assembled structure block.
+#[derive(Default)]
+pub struct Structure(pub Vec<u8>);
+
+impl Structure {
+    pub fn token(mut self, token: u32) -> Self {
+        self.0.extend_from_slice(&token.to_be_bytes());
+        self
+    }
+
+    pub fn begin_node(self, name: &str) -> Self {
+        let mut s = self.token(BEGIN_NODE);
+        s.0.extend_from_slice(name.as_bytes());
+        s.0.push(0);
+        s.pad()
+    }
+
+    pub fn prop(self, name_offset: u32, value: &[u8]) -> Self {
+        let mut s = self
+            .token(PROP)
+            .token(value.len() as u32)
+            .token(name_offset);
+        s.0.extend_from_slice(value);
+        s.pad()
+    }
+
+    fn pad(mut self) -> Self {
+        while !self.0.len().is_multiple_of(4) {
+            self.0.push(0);
+        }
+        self
+    }
+}
+
+/// Wrap a structure and strings block into a version 17 blob with an empty
+/// memory reservation block.
+pub fn blob(structure: &[u8], strings: &[u8]) -> Vec<u8> {
+    let off_mem_rsvmap = 40;
+    let off_dt_struct = off_mem_rsvmap + 16;
+    let off_dt_strings = off_dt_struct + structure.len();
+    let totalsize = off_dt_strings + strings.len();
+
+    let mut dtb = Vec::new();
+    for field in [
+        0xd00dfeed,
+        totalsize as u32,
+        off_dt_struct as u32,
+        off_dt_strings as u32,
+        off_mem_rsvmap as u32,
+        17,
+        16,
+        0,
+        strings.len() as u32,
+        structure.len() as u32,
+    ] {
+        dtb.extend_from_slice(&field.to_be_bytes());
+    }
+    dtb.extend_from_slice(&[0; 16]);
+    dtb.extend_from_slice(structure);
+    dtb.extend_from_slice(strings);
+    dtb
+}
@@ -0,0 +1,99 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn rpi_cpus() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let cpus: Vec<_> = dt.cpus().collect();
+    assert_eq!(cpus.len(), 4);
+    assert_eq!(cpus[1].node.name(), "cpu@1");
+    assert_eq!(cpus[1].id, 0xf01);
+    assert_eq!(cpus[1].compatible, Some("arm,cortex-a7"));
+    assert_eq!(cpus[1].status, NodeStatus::Okay);
+    assert_eq!(cpus[1].enable_method, None);
+    assert_eq!(cpus[1].clock_frequency, Some(800_000_000));
+    assert_eq!(cpus[1].timebase_frequency, None);
+    assert_eq!(dt.cpu_count(), 4);
+}
+
+#[test]
+fn riscv_harts() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    let mut cpus = root.child("cpus");
+    cpus.property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 0)
+        .property_u32("timebase-frequency", 10_000_000);
+    cpus.child("cpu-map").child("cluster0");
+    cpus.child("cpu@0")
+        .property_str("device_type", "cpu")
+        .property_u32("reg", 0)
+        .property_str("status", "disabled")
+        .property_str("compatible", "sifive,e51");
+    cpus.child("cpu@1")
+        .property_str("device_type", "cpu")
+        .property_u32("reg", 1)
+        .property_str("enable-method", "sbi")
+        .property("clock-frequency", &[0, 0, 0, 1, 0, 0, 0, 0]);
+    cpus.child("cpu@2")
+        .property_str("device_type", "cpu")
+        .property_u32("reg", 2)
+        .property_u32("timebase-frequency", 1_000_000);
+    // no reg
+    cpus.child("cpu").property_str("device_type", "cpu");
+    let dt = builder.build().unwrap();
+
+    let cpus: Vec<_> = dt.cpus().collect();
+    let ids: Vec<u64> = cpus.iter().map(|cpu| cpu.id).collect();
+    assert_eq!(ids, [0, 1, 2]);
+    assert_eq!(cpus[0].status, NodeStatus::Disabled);
+    assert_eq!(cpus[1].enable_method, Some("sbi"));
+    assert_eq!(cpus[1].clock_frequency, Some(0x1_0000_0000));
+    assert_eq!(cpus[1].timebase_frequency, Some(10_000_000));
+    assert_eq!(cpus[2].timebase_frequency, Some(1_000_000));
+    assert_eq!(dt.cpu_count(), 2);
+
+    assert_eq!(DeviceTreeBuilder::new().build().unwrap().cpu_count(), 0);
+}
+
+fn arm_cpus(address_cells: u32, boot_cpuid_phys: u32) -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    let mut cpus = root.child("cpus");
+    cpus.property_u32("#address-cells", address_cells)
+        .property_u32("#size-cells", 0);
+    for (name, mpidr) in [("cpu@0", 0u64), ("cpu@100", 0x100)] {
+        let reg = if address_cells == 2 {
+            (mpidr | 0x1_0000_0000).to_be_bytes().to_vec()
+        } else {
+            (mpidr as u32).to_be_bytes().to_vec()
+        };
+        cpus.child(name)
+            .property_str("device_type", "cpu")
+            .property("reg", &reg);
+    }
+    let mut dt = builder.build().unwrap();
+    dt.boot_cpuid_phys = boot_cpuid_phys;
+    dt
+}
+
+#[test]
+fn boot_cpu_by_id() {
+    let dt = arm_cpus(1, 0x100);
+    assert_eq!(dt.boot_cpu().unwrap().name(), "cpu@100");
+
+    // only the low cell of the 64-bit IDs matches
+    let dt = arm_cpus(2, 0x100);
+    assert_eq!(dt.boot_cpu().unwrap().name(), "cpu@100");
+
+    let dt = arm_cpus(1, 0x200);
+    assert!(dt.boot_cpu().is_none());
+
+    // the firmware fills in boot_cpuid_phys, so nothing matches
+    let dt = DeviceTree::load(DTB).unwrap();
+    assert!(dt.boot_cpu().is_none());
+}
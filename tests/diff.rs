@@ -0,0 +1,110 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+fn before() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_str("model", "board");
+    let mut soc = root.child("soc");
+    soc.child("uart@1000")
+        .property_str("status", "okay")
+        .property_u32("clock-frequency", 48_000_000);
+    soc.child("i2c@2000").child("eeprom@50");
+    builder.build().unwrap()
+}
+
+fn after() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_str("model", "board");
+    root.property_str("serial-number", "1234");
+    let mut soc = root.child("soc");
+    soc.child("uart@1000").property_str("status", "disabled");
+    soc.child("spi@3000")
+        .property_u32("#address-cells", 1)
+        .child("flash@0")
+        .property_str("compatible", "jedec,spi-nor");
+    builder.build().unwrap()
+}
+
+#[test]
+fn identical_trees() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.diff(&dt.clone()), []);
+}
+
+#[test]
+fn lists_differences_in_order() {
+    assert_eq!(
+        before().diff(&after()),
+        [
+            TreeDiff::PropertyAdded {
+                path: "/".into(),
+                name: "serial-number".into(),
+                value: b"1234\0".to_vec(),
+            },
+            TreeDiff::NodeRemoved("/soc/i2c@2000".into()),
+            TreeDiff::PropertyChanged {
+                path: "/soc/uart@1000".into(),
+                name: "status".into(),
+                old: b"okay\0".to_vec(),
+                new: b"disabled\0".to_vec(),
+            },
+            TreeDiff::PropertyRemoved {
+                path: "/soc/uart@1000".into(),
+                name: "clock-frequency".into(),
+            },
+            TreeDiff::NodeAdded("/soc/spi@3000".into()),
+            TreeDiff::PropertyAdded {
+                path: "/soc/spi@3000".into(),
+                name: "#address-cells".into(),
+                value: vec![0, 0, 0, 1],
+            },
+            TreeDiff::NodeAdded("/soc/spi@3000/flash@0".into()),
+            TreeDiff::PropertyAdded {
+                path: "/soc/spi@3000/flash@0".into(),
+                name: "compatible".into(),
+                value: b"jedec,spi-nor\0".to_vec(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn apply_diff_round_trips() {
+    let before = before();
+    let after = after();
+
+    let diff = before.diff(&after);
+    assert_eq!(before.apply_diff(&diff), Some(after.clone()));
+
+    let diff = after.diff(&before);
+    assert_eq!(after.apply_diff(&diff), Some(before));
+}
+
+#[test]
+fn apply_diff_to_fixture() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let mut changed = dt.clone();
+    changed.find_node_mut("/soc").unwrap().remove_child("leds");
+    changed
+        .find_node_mut("/soc/uart@7e201000")
+        .unwrap()
+        .set_property("status", b"disabled\0".to_vec())
+        .unwrap();
+
+    let diff = dt.diff(&changed);
+    assert_eq!(diff.len(), 2);
+    assert_eq!(dt.apply_diff(&diff), Some(changed));
+}
+
+#[test]
+fn apply_diff_to_wrong_tree() {
+    let diff = before().diff(&after());
+
+    assert_eq!(DeviceTree::load(DTB).unwrap().apply_diff(&diff), None);
+}
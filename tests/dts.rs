@@ -0,0 +1,101 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn small_tree_to_dts() {
+    let mut uart = Node::new("uart@1000");
+    uart.set_property("compatible", b"vendor,uart\0ns16550a\0".to_vec())
+        .unwrap();
+    uart.set_property("reg", vec![0, 0, 0x10, 0, 0, 0, 0x1, 0])
+        .unwrap();
+    uart.set_property("mac-address", vec![0x02, 0, 0, 0xaa, 0xbb, 0xcc])
+        .unwrap();
+    uart.set_property("dma-coherent", Vec::new()).unwrap();
+
+    let mut root = Node::new("");
+    root.set_property("#address-cells", vec![0, 0, 0, 1])
+        .unwrap();
+    root.set_property("model", b"Test \"board\"\0".to_vec())
+        .unwrap();
+    root.add_child(uart).unwrap();
+    root.add_child(Node::new("chosen")).unwrap();
+    let mut dt = DeviceTree::new(root);
+    dt.reserved.push(MemoryReservation {
+        address: 0x1000,
+        size: 0x2000,
+    });
+
+    let expected = "\
+/dts-v1/;
+
+/memreserve/ 0x1000 0x2000;
+
+/ {
+\t#address-cells = <0x1>;
+\tmodel = \"Test \\\"board\\\"\";
+
+\tuart@1000 {
+\t\tcompatible = \"vendor,uart\", \"ns16550a\";
+\t\treg = <0x1000 0x100>;
+\t\tmac-address = [02 00 00 aa bb cc];
+\t\tdma-coherent;
+\t};
+
+\tchosen {
+\t};
+};
+";
+    assert_eq!(dt.to_dts(), expected);
+}
+
+#[test]
+fn parsed_tree_to_dts() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let dts = dt.to_dts();
+
+    assert!(dts.starts_with("/dts-v1/;\n\n/ {\n"));
+    assert!(dts.contains("\tmodel = \"Raspberry Pi 2 Model B\";\n"));
+    assert!(dts.contains("\t\tuart@7e201000 {\n"));
+    assert!(
+        dts.contains("\t\t\tcompatible = \"arm,pl011\", \"arm,primecell\";\n")
+    );
+    assert!(dts.contains("\t\t\tgpio-controller;\n"));
+    // an empty string is not printed as a string list
+    assert!(dts.contains("\t\tbootargs = [00];\n"));
+
+    let opened = dts.matches(" {\n").count();
+    let closed = dts.matches("};\n").count();
+    assert_eq!(opened, dt.iter_dfs().count());
+    assert_eq!(opened, closed);
+}
+
+#[test]
+fn dts_compiles_back_to_same_tree() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let dtc = Command::new("dtc")
+        .args(["-q", "-I", "dts", "-O", "dtb", "-V", "17"])
+        .args(["-b", &dt.boot_cpuid_phys.to_string(), "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn();
+    let Ok(mut dtc) = dtc else {
+        eprintln!("dtc not found in PATH, skipping");
+        return;
+    };
+    // dtc reads all of its input before writing anything
+    let mut stdin = dtc.stdin.take().unwrap();
+    stdin.write_all(dt.to_dts().as_bytes()).unwrap();
+    drop(stdin);
+    let output = dtc.wait_with_output().unwrap();
+    assert!(output.status.success(), "dtc failed: {:?}", output.status);
+
+    let reloaded = DeviceTree::load(&output.stdout).unwrap();
+    assert_eq!(reloaded, dt);
+}
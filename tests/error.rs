@@ -0,0 +1,73 @@
+extern crate flat_device_tree;
+
+use std::error::Error as _;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn display_messages() {
+    assert_eq!(
+        Error::InvalidMagicNumber.to_string(),
+        "invalid FDT magic number"
+    );
+    assert_eq!(
+        Error::ParseError(0x38).to_string(),
+        "unexpected data at offset 0x38"
+    );
+    assert_eq!(
+        PropError::UnknownPhandle(0x17).to_string(),
+        "no node with phandle 0x17"
+    );
+    assert_eq!(NodeError::InvalidNodeName.to_string(), "invalid node name");
+    assert_eq!(
+        GraftError::ParentNotFound.to_string(),
+        "parent node not found"
+    );
+    assert_eq!(
+        RenameError::RootNode.to_string(),
+        "cannot rename the root node"
+    );
+    assert_eq!(
+        SliceReadError::UnexpectedEndOfInput(0x40).to_string(),
+        "unexpected end of input at offset 0x40"
+    );
+}
+
+#[test]
+fn error_sources() {
+    let err = Error::SliceReadError(SliceReadError::UnexpectedEndOfInput(0));
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "unexpected end of input at offset 0x0"
+    );
+
+    let err =
+        Error::from(PropError::from(SliceReadError::UnexpectedEndOfInput(0)));
+    let source = err.source().unwrap();
+    assert_eq!(source.to_string(), "failed to read property value");
+    assert_eq!(
+        source.source().unwrap().to_string(),
+        "unexpected end of input at offset 0x0"
+    );
+
+    assert!(Error::InvalidMagicNumber.source().is_none());
+}
+
+fn load(
+    buf: &[u8],
+) -> std::result::Result<DeviceTree, Box<dyn std::error::Error>> {
+    Ok(DeviceTree::load(buf)?)
+}
+
+#[test]
+fn boxed_errors() {
+    assert!(load(DTB).is_ok());
+
+    let err = load(&DTB[..64]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "device tree size does not match the buffer"
+    );
+}
@@ -0,0 +1,56 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::glob::component_matches;
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+fn names<'a>(nodes: impl Iterator<Item = &'a Node>) -> Vec<&'a str> {
+    nodes.map(|n| n.name()).collect()
+}
+
+#[test]
+fn component_wildcards() {
+    assert!(component_matches("uart@*", "uart@7e201000"));
+    assert!(component_matches("*", "soc"));
+    assert!(component_matches("*@7e2*", "gpio@7e200000"));
+    assert!(component_matches("i2c*@*", "i2c@7e205000"));
+    assert!(component_matches("clock@0", "clock@0"));
+
+    assert!(!component_matches("uart@*", "uart"));
+    assert!(!component_matches("clock@0", "clock@1"));
+    assert!(!component_matches("*@1", "clock@0"));
+}
+
+#[test]
+fn glob_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(
+        names(dt.find_nodes_glob("/soc/uart@*")),
+        ["uart@7e201000", "uart@7e215040"]
+    );
+    assert_eq!(
+        names(dt.find_nodes_glob("/*/cpu@*")),
+        ["cpu@0", "cpu@1", "cpu@2", "cpu@3"]
+    );
+    assert_eq!(
+        names(dt.find_nodes_glob("/soc/*/spidev@*")),
+        ["spidev@0", "spidev@1"]
+    );
+    assert_eq!(names(dt.find_nodes_glob("/")), [""]);
+    assert_eq!(dt.find_nodes_glob("/soc/ethernet@*").count(), 0);
+}
+
+#[test]
+fn glob_any_depth() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let all = dt.find_nodes_glob("/**").count();
+    let below_soc = dt.find_nodes_glob("/soc/**").count();
+    let soc_children = dt.find_nodes_glob("/soc/*").count();
+
+    assert!(below_soc >= soc_children);
+    assert!(all > below_soc);
+    assert!(dt.find_nodes_glob("/soc/**").all(|n| n.name() != "soc"));
+}
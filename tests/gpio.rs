@@ -0,0 +1,132 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn rpi_gpios() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let led = dt.find("/soc/leds/act").unwrap();
+
+    let gpios = led.gpios(&dt, "gpios").unwrap();
+    assert_eq!(gpios.len(), 1);
+    assert_eq!(gpios[0].controller.name(), "gpio@7e200000");
+    assert_eq!(gpios[0].args, [47, 0]);
+    assert_eq!(gpios[0].line(), Some(47));
+    assert!(!gpios[0].is_active_low());
+
+    // native chip selects, i.e. empty entries
+    let spi = dt.find("/soc/spi@7e204000").unwrap();
+    assert_eq!(spi.gpios(&dt, "cs"), Ok(Vec::new()));
+}
+
+fn board() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("gpio1")
+        .property_u32("#gpio-cells", 2)
+        .property_u32("phandle", 1);
+    root.child("legacy")
+        .property_u32("#gpio-cells", 1)
+        .property_u32("phandle", 2);
+
+    let mut mmc = root.child("mmc");
+    // <&gpio1 6 GPIO_ACTIVE_LOW>
+    mmc.property("cd-gpios", &[0, 0, 0, 1, 0, 0, 0, 6, 0, 0, 0, 1])
+        .property("wp-gpio", &[0, 0, 0, 2, 0, 0, 0, 3])
+        // <&gpio1 1 0>, <&legacy 4>, <&gpio1 2 0>
+        .property(
+            "gpios",
+            &[
+                0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 4, 0,
+                0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 0,
+            ],
+        )
+        .property("gpio-names", b"reset\0power\0")
+        .property("bad-gpios", &[0, 0, 0, 1, 0, 0, 0, 6]);
+    builder.build().unwrap()
+}
+
+#[test]
+fn card_detect_gpio() {
+    let dt = board();
+    let mmc = dt.find("/mmc").unwrap();
+
+    for prop in ["cd", "cd-gpios"] {
+        let cd = mmc.gpios(&dt, prop).unwrap();
+        assert_eq!(cd.len(), 1);
+        assert_eq!(cd[0].controller.name(), "gpio1");
+        assert_eq!(cd[0].line(), Some(6));
+        assert!(cd[0].is_active_low());
+        assert_eq!(cd[0].name, None);
+    }
+}
+
+#[test]
+fn legacy_gpios() {
+    let dt = board();
+    let mmc = dt.find("/mmc").unwrap();
+
+    let wp = mmc.gpios(&dt, "wp").unwrap();
+    assert_eq!(wp[0].controller.name(), "legacy");
+    assert_eq!(wp[0].args, [3]);
+    assert_eq!(wp[0].flags(), 0);
+}
+
+#[test]
+fn named_gpios() {
+    let dt = board();
+    let mmc = dt.find("/mmc").unwrap();
+
+    let gpios: Vec<_> = mmc
+        .gpios(&dt, "gpios")
+        .unwrap()
+        .into_iter()
+        .map(|gpio| (gpio.controller.name(), gpio.line(), gpio.name))
+        .collect();
+    assert_eq!(
+        gpios,
+        [
+            ("gpio1", Some(1), Some("reset")),
+            ("legacy", Some(4), Some("power")),
+            ("gpio1", Some(2), None),
+        ]
+    );
+}
+
+#[test]
+fn gpio_errors() {
+    let dt = board();
+    let mmc = dt.find("/mmc").unwrap();
+
+    assert_eq!(
+        mmc.gpios(&dt, "reset"),
+        Err(Error::PropError(PropError::NotFound))
+    );
+    assert_eq!(
+        mmc.gpios(&dt, "bad"),
+        Err(Error::PropError(PropError::Truncated))
+    );
+}
+
+#[test]
+fn function_name_is_not_a_property_name() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("gpio1")
+        .property_u32("#gpio-cells", 2)
+        .property_u32("phandle", 1);
+    root.child("phy")
+        // an unrelated property named like the function
+        .property_u32("reset", 1)
+        // <&gpio1 9 0>
+        .property("reset-gpios", &[0, 0, 0, 1, 0, 0, 0, 9, 0, 0, 0, 0]);
+    let dt = builder.build().unwrap();
+    let phy = dt.find("/phy").unwrap();
+
+    let reset = phy.gpios(&dt, "reset").unwrap();
+    assert_eq!(reset.len(), 1);
+    assert_eq!(reset[0].line(), Some(9));
+    assert_eq!(phy.gpios(&dt, "reset-gpios"), Ok(reset));
+}
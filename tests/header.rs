@@ -0,0 +1,34 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn load_header_only() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let header = DeviceTreeHeader::load(&DTB[..DeviceTreeHeader::SIZE]);
+
+    assert_eq!(header.as_ref(), Ok(dt.header()));
+    assert_eq!(DeviceTreeHeader::load(DTB), header);
+}
+
+#[test]
+fn truncated_header() {
+    assert_eq!(
+        DeviceTreeHeader::load(&DTB[..DeviceTreeHeader::SIZE - 1]),
+        Err(Error::SliceReadError(SliceReadError::UnexpectedEndOfInput(
+            36
+        )))
+    );
+    assert_eq!(
+        DeviceTreeHeader::load(&DTB[..2]),
+        Err(Error::SliceReadError(SliceReadError::UnexpectedEndOfInput(
+            0
+        )))
+    );
+    assert_eq!(
+        DeviceTreeHeader::load(&[0; DeviceTreeHeader::SIZE]),
+        Err(Error::InvalidMagicNumber)
+    );
+}
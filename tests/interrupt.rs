@@ -0,0 +1,282 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn rpi_interrupts() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+
+    let irqs = dt.interrupts(uart).unwrap();
+    assert_eq!(irqs.len(), 1);
+    assert_eq!(irqs[0].controller.name(), "interrupt-controller@7e00b200");
+    assert_eq!(irqs[0].cells, [2, 25]);
+
+    // no interrupts property
+    assert_eq!(dt.interrupts(dt.find("/soc").unwrap()), Ok(Vec::new()));
+}
+
+/// Controllers with one, two and three cells, and devices using them.
+fn board() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("interrupt-parent", 3);
+    root.child("plic")
+        .property("interrupt-controller", &[])
+        .property_u32("#interrupt-cells", 1)
+        .property_u32("phandle", 1);
+    root.child("intc")
+        .property("interrupt-controller", &[])
+        .property_u32("#interrupt-cells", 2)
+        .property_u32("phandle", 2);
+    root.child("gic")
+        .property("interrupt-controller", &[])
+        .property_u32("#interrupt-cells", 3)
+        .property_u32("phandle", 3);
+
+    let mut soc = root.child("soc");
+    soc.property_u32("interrupt-parent", 1);
+    // inherits the plic from soc
+    soc.child("uart@1000")
+        .property("interrupts", &[0, 0, 0, 9, 0, 0, 0, 10]);
+    // has its own parent
+    soc.child("gpio@2000")
+        .property_u32("interrupt-parent", 2)
+        .property("interrupts", &[0, 0, 0, 5, 0, 0, 0, 4]);
+    // inherits the gic from the root
+    root.child("timer")
+        .property("interrupts", &[0, 0, 0, 1, 0, 0, 0, 13, 0, 0, 0, 4]);
+
+    builder.build().unwrap()
+}
+
+fn specifiers(dt: &DeviceTree, path: &str) -> Vec<(String, Vec<u32>)> {
+    dt.interrupts(dt.find(path).unwrap())
+        .unwrap()
+        .into_iter()
+        .map(|irq| (irq.controller.name().to_owned(), irq.cells))
+        .collect()
+}
+
+#[test]
+fn inherited_interrupt_parents() {
+    let dt = board();
+
+    assert_eq!(
+        specifiers(&dt, "/soc/uart@1000"),
+        [("plic".to_owned(), vec![9]), ("plic".to_owned(), vec![10])]
+    );
+    assert_eq!(
+        specifiers(&dt, "/soc/gpio@2000"),
+        [("intc".to_owned(), vec![5, 4])]
+    );
+    assert_eq!(
+        specifiers(&dt, "/timer"),
+        [("gic".to_owned(), vec![1, 13, 4])]
+    );
+}
+
+#[test]
+fn implicit_interrupt_parents() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("interrupt-parent", 1);
+    root.child("gic")
+        .property("interrupt-controller", &[])
+        .property_u32("#interrupt-cells", 3)
+        .property_u32("phandle", 1);
+
+    let mut intc = root.child("intc");
+    intc.property("interrupt-controller", &[])
+        .property_u32("#interrupt-cells", 1)
+        .property("interrupts", &[0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 4]);
+    // the tree parent is the controller, even though the root names
+    // another one
+    intc.child("timer").property_u32("interrupts", 3);
+    // and the same goes for its descendants, up to the controller
+    intc.child("bus").child("dev").property_u32("interrupts", 5);
+
+    let mut pcie = root.child("pcie");
+    pcie.property("interrupt-map", &[])
+        .property_u32("#interrupt-cells", 1);
+    pcie.child("ep").property_u32("interrupts", 1);
+    let dt = builder.build().unwrap();
+
+    let parent = |path| {
+        let node = dt.find(path).unwrap();
+        dt.interrupt_parent(node).unwrap().name()
+    };
+    assert_eq!(parent("/intc/timer"), "intc");
+    assert_eq!(parent("/intc/bus/dev"), "intc");
+    assert_eq!(parent("/pcie/ep"), "pcie");
+    // a controller's own interrupts go to the parent the root names
+    assert_eq!(parent("/intc"), "gic");
+
+    assert_eq!(
+        specifiers(&dt, "/intc/timer"),
+        [("intc".to_owned(), vec![3])]
+    );
+    assert_eq!(
+        specifiers(&dt, "/intc"),
+        [("gic".to_owned(), vec![0, 7, 4])]
+    );
+}
+
+#[test]
+fn interrupt_errors() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("orphan").property_u32("interrupts", 1);
+    root.child("dangling")
+        .property_u32("interrupt-parent", 7)
+        .property_u32("interrupts", 1);
+    root.child("intc")
+        .property_u32("#interrupt-cells", 2)
+        .property_u32("phandle", 1);
+    root.child("short")
+        .property_u32("interrupt-parent", 1)
+        .property("interrupts", &[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3]);
+    root.child("nocells")
+        .property_u32("interrupt-parent", 2)
+        .property_u32("interrupts", 1);
+    root.child("plain").property_u32("phandle", 2);
+    let dt = builder.build().unwrap();
+
+    let cases = [
+        ("/orphan", PropError::MissingInterruptParent),
+        ("/dangling", PropError::UnknownPhandle(7)),
+        ("/short", PropError::Truncated),
+        ("/nocells", PropError::MissingCellCount),
+    ];
+    for (path, err) in cases {
+        assert_eq!(
+            dt.interrupts(dt.find(path).unwrap()),
+            Err(Error::PropError(err)),
+            "{}",
+            path
+        );
+    }
+}
+
+#[test]
+fn extended_interrupts() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("clint")
+        .property_u32("#interrupt-cells", 1)
+        .property_u32("phandle", 1);
+    root.child("plic")
+        .property_u32("#interrupt-cells", 2)
+        .property_u32("phandle", 2);
+    // <&clint 3>, <&plic 9 4>, <&clint 7>
+    root.child("dev").property(
+        "interrupts-extended",
+        &[
+            0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 9, 0, 0, 0, 4, 0, 0,
+            0, 1, 0, 0, 0, 7,
+        ],
+    );
+    root.child("broken")
+        .property("interrupts-extended", &[0, 0, 0, 2, 0, 0, 0, 9]);
+    let dt = builder.build().unwrap();
+
+    let irqs: Vec<_> = dt
+        .interrupts_extended(dt.find("/dev").unwrap())
+        .unwrap()
+        .into_iter()
+        .map(|irq| (irq.controller.name().to_owned(), irq.cells))
+        .collect();
+    assert_eq!(
+        irqs,
+        [
+            ("clint".to_owned(), vec![3]),
+            ("plic".to_owned(), vec![9, 4]),
+            ("clint".to_owned(), vec![7]),
+        ]
+    );
+
+    assert_eq!(
+        dt.interrupts_extended(dt.find("/broken").unwrap()),
+        Err(Error::PropError(PropError::Truncated))
+    );
+    assert_eq!(dt.interrupts_extended(&dt.root), Ok(Vec::new()));
+}
+
+fn cells(cells: &[u32]) -> Vec<u8> {
+    cells.iter().flat_map(|cell| cell.to_be_bytes()).collect()
+}
+
+/// A PCI host bridge routing INTA of slots 0 and 1 to different GIC
+/// interrupts.
+fn pci_tree(map: &[u32]) -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("gic")
+        .property_u32("#interrupt-cells", 3)
+        .property_u32("#address-cells", 0)
+        .property_u32("phandle", 1);
+    root.child("pcie@10000000")
+        .property_u32("#address-cells", 3)
+        .property_u32("#size-cells", 2)
+        .property_u32("#interrupt-cells", 1)
+        .property("interrupt-map-mask", &cells(&[0x1800, 0, 0, 7]))
+        .property("interrupt-map", &cells(map));
+    builder.build().unwrap()
+}
+
+const PCI_MAP: &[u32] = &[
+    // slot 0, INTA -> GIC SPI 32
+    0x0000, 0, 0, 1, 1, 0, 32, 4, //
+    // slot 1, INTA -> GIC SPI 33
+    0x0800, 0, 0, 1, 1, 0, 33, 4,
+];
+
+#[test]
+fn map_pci_interrupts() {
+    let dt = pci_tree(PCI_MAP);
+    let bridge = dt.find("/pcie@10000000").unwrap();
+
+    // device 1, function 2: the function bits are masked away
+    let irq = dt.map_interrupt(bridge, &[0x0a00, 0, 0], &[1]).unwrap();
+    assert_eq!(irq.controller.name(), "gic");
+    assert_eq!(irq.cells, [0, 33, 4]);
+
+    let irq = dt.map_interrupt(bridge, &[0, 0, 0], &[1]).unwrap();
+    assert_eq!(irq.cells, [0, 32, 4]);
+
+    // INTB is not mapped
+    assert_eq!(
+        dt.map_interrupt(bridge, &[0, 0, 0], &[2]),
+        Err(Error::PropError(PropError::UnmappedInterrupt))
+    );
+    assert_eq!(
+        dt.map_interrupt(bridge, &[0, 0], &[1]),
+        Err(Error::PropError(PropError::InvalidCellCount))
+    );
+}
+
+#[test]
+fn map_malformed() {
+    // the last entry is missing a cell of the parent specifier
+    let dt = pci_tree(&PCI_MAP[..PCI_MAP.len() - 1]);
+    let bridge = dt.find("/pcie@10000000").unwrap();
+
+    assert_eq!(
+        dt.map_interrupt(bridge, &[0, 0, 0], &[1]),
+        Err(Error::PropError(PropError::Truncated))
+    );
+
+    let dt = pci_tree(&[0, 0, 0, 1, 9]);
+    let bridge = dt.find("/pcie@10000000").unwrap();
+    assert_eq!(
+        dt.map_interrupt(bridge, &[0, 0, 0], &[1]),
+        Err(Error::PropError(PropError::UnknownPhandle(9)))
+    );
+
+    assert_eq!(
+        dt.map_interrupt(&dt.root, &[0, 0], &[1]),
+        Err(Error::PropError(PropError::NotFound))
+    );
+}
@@ -0,0 +1,268 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+fn node(name: &str, children: Vec<Node>) -> Node {
+    let mut node = Node::new(name);
+    for child in children {
+        node.add_child(child).unwrap();
+    }
+    node
+}
+
+fn tree() -> DeviceTree {
+    DeviceTree::new(node(
+        "",
+        vec![
+            node(
+                "a",
+                vec![node("a1", vec![node("a1x", vec![])]), node("a2", vec![])],
+            ),
+            node("b", vec![]),
+            node("c", vec![node("c1", vec![])]),
+        ],
+    ))
+}
+
+#[test]
+fn depth_first() {
+    let dt = tree();
+
+    let names: Vec<_> = dt.iter_dfs().map(|n| n.name()).collect();
+    assert_eq!(names, ["", "a", "a1", "a1x", "a2", "b", "c", "c1"]);
+}
+
+#[test]
+fn depth_first_with_depth() {
+    let dt = tree();
+
+    let depths: Vec<_> = dt
+        .iter_dfs_with_depth()
+        .map(|(depth, n)| (depth, n.name()))
+        .collect();
+    assert_eq!(
+        depths,
+        [
+            (0, ""),
+            (1, "a"),
+            (2, "a1"),
+            (3, "a1x"),
+            (2, "a2"),
+            (1, "b"),
+            (1, "c"),
+            (2, "c1")
+        ]
+    );
+}
+
+#[test]
+fn depth_first_with_paths() {
+    let dt = tree();
+
+    let paths: Vec<_> = dt.iter_with_paths().map(|(path, _)| path).collect();
+    assert_eq!(
+        paths,
+        [
+            "/",
+            "/a",
+            "/a/a1",
+            "/a/a1/a1x",
+            "/a/a2",
+            "/b",
+            "/c",
+            "/c/c1"
+        ]
+    );
+}
+
+#[test]
+fn paths_lead_to_their_nodes() {
+    let dt =
+        DeviceTree::load(include_bytes!("../examples/bcm2709-rpi-2-b.dtb"))
+            .unwrap();
+
+    assert_eq!(dt.iter_with_paths().count(), dt.iter().count());
+    for (path, node) in dt.iter_with_paths() {
+        assert!(std::ptr::eq(dt.find(&path).unwrap(), node), "{}", path);
+        assert_eq!(dt.node_path(node).unwrap(), path);
+    }
+}
+
+#[test]
+fn breadth_first() {
+    let dt = tree();
+
+    let names: Vec<_> = dt.iter_bfs().map(|n| n.name()).collect();
+    assert_eq!(names, ["", "a", "b", "c", "a1", "a2", "c1", "a1x"]);
+}
+
+#[test]
+fn breadth_first_from_node() {
+    let dt = tree();
+    let names = |max_depth| -> Vec<_> {
+        dt.root
+            .iter_breadth_first(max_depth)
+            .map(|n| n.name())
+            .collect()
+    };
+
+    assert_eq!(names(None), ["", "a", "b", "c", "a1", "a2", "c1", "a1x"]);
+    assert_eq!(names(Some(0)), [""]);
+    assert_eq!(names(Some(1)), ["", "a", "b", "c"]);
+    assert_eq!(names(Some(2)), ["", "a", "b", "c", "a1", "a2", "c1"]);
+
+    let a = dt.find("/a").unwrap();
+    let names: Vec<_> =
+        a.iter_breadth_first(Some(1)).map(|n| n.name()).collect();
+    assert_eq!(names, ["a", "a1", "a2"]);
+}
+
+#[test]
+fn breadth_and_depth_first_find_different_nodes() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("bus")
+        .child("uart@2000")
+        .property_str("compatible", "ns16550a");
+    root.child("uart@1000")
+        .property_str("compatible", "ns16550a");
+    let dt = builder.build().unwrap();
+
+    // both iterators share an item type, so the same filter works on either
+    fn uart<'a>(mut nodes: impl Iterator<Item = &'a Node>) -> &'a str {
+        let uart = nodes.find(|n| n.is_compatible("ns16550a")).unwrap();
+        uart.name()
+    }
+
+    assert_eq!(uart(dt.root.iter()), "uart@2000");
+    assert_eq!(uart(dt.root.iter_breadth_first(None)), "uart@1000");
+}
+
+#[test]
+fn deep_tree() {
+    let mut root = node("n", vec![]);
+    let mut last = &mut root;
+    for i in 0..100_000 {
+        last.add_child(Node::new(if i < 99_999 { "n" } else { "leaf" }))
+            .unwrap();
+        last = &mut last.children_mut()[0];
+    }
+    let dt = DeviceTree::new(root);
+
+    assert_eq!(dt.iter_dfs().count(), 100_001);
+    assert_eq!(dt.iter_bfs().last().unwrap().name(), "leaf");
+    assert_eq!(dt.iter_dfs_with_depth().last().unwrap().0, 100_000);
+
+    // dropping a deep tree recurses, so take it apart iteratively
+    let mut next = Some(dt.root);
+    while let Some(mut node) = next {
+        next = node
+            .children_mut()
+            .first_mut()
+            .map(|child| std::mem::replace(child, Node::new("")));
+    }
+}
+
+#[test]
+fn iter_is_depth_first() {
+    let dt = tree();
+
+    assert!(dt.iter().eq(dt.iter_dfs()));
+
+    let a = dt.find("/a").unwrap();
+    let names: Vec<_> = a.iter().map(|n| n.name()).collect();
+    assert_eq!(names, ["a", "a1", "a1x", "a2"]);
+}
+
+#[test]
+fn iter_size_hint() {
+    let dt = tree();
+    let mut iter = dt.iter();
+
+    assert_eq!(iter.size_hint(), (1, None));
+    iter.next();
+    // a, b and c are known, their children are not
+    assert_eq!(iter.size_hint(), (3, None));
+    iter.next();
+    assert_eq!(iter.size_hint(), (4, None));
+    assert_eq!(iter.by_ref().count(), 6);
+    assert_eq!(iter.size_hint(), (0, None));
+}
+
+#[test]
+fn iter_in_document_order() {
+    let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    let dt = DeviceTree::load(buf).unwrap();
+
+    // the order in which dtc writes, and prints, the nodes
+    let document: Vec<_> = StreamingParser::new(buf)
+        .unwrap()
+        .filter_map(|event| match event {
+            Ok(ParseEvent::BeginNode { name }) => Some(name),
+            _ => None,
+        })
+        .collect();
+    let names: Vec<_> = dt.iter().map(|n| n.name()).collect();
+
+    assert_eq!(names, document);
+    assert_eq!(&names[..4], ["", "chosen", "aliases", "memory"]);
+}
+
+#[test]
+fn properties_with_paths_in_document_order() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 1);
+    let mut soc = root.child("soc");
+    soc.property_str("compatible", "simple-bus");
+    soc.child("uart@1000")
+        .property_str("status", "okay")
+        .property_u32("reg", 0x1000);
+    soc.property("ranges", &[]);
+    root.child("chosen").property_str("bootargs", "quiet");
+    let dt = builder.build().unwrap();
+
+    let props: Vec<_> = dt
+        .properties_with_paths()
+        .map(|(path, prop)| format!("{}:{}", path, prop.name()))
+        .collect();
+    // the properties of a node come before those of its children
+    assert_eq!(
+        props,
+        [
+            "/:#address-cells",
+            "/soc:compatible",
+            "/soc:ranges",
+            "/soc/uart@1000:status",
+            "/soc/uart@1000:reg",
+            "/chosen:bootargs",
+        ]
+    );
+}
+
+#[test]
+fn properties_with_paths_cover_the_tree() {
+    let dt =
+        DeviceTree::load(include_bytes!("../examples/bcm2709-rpi-2-b.dtb"))
+            .unwrap();
+
+    assert_eq!(dt.properties_with_paths().count(), 439);
+
+    let expected = dt.iter_with_paths().flat_map(|(path, node)| {
+        node.properties()
+            .iter()
+            .map(move |prop| (path.clone(), prop))
+    });
+    assert!(dt.properties_with_paths().eq(expected));
+
+    // nodes without properties are skipped
+    let empty = Node::new("");
+    assert_eq!(DeviceTree::new(empty).properties_with_paths().count(), 0);
+
+    let parents: Vec<_> = dt
+        .properties_with_paths()
+        .filter(|(_, prop)| prop.name() == "interrupt-parent")
+        .map(|(path, prop)| (path, prop.as_u32()))
+        .collect();
+    assert_eq!(parents, [("/".to_owned(), Some(1))]);
+}
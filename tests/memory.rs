@@ -0,0 +1,199 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn memory_regions_32bit() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1);
+    root.child("memory@0")
+        .property_str("device_type", "memory")
+        .property(
+            "reg",
+            &[0, 0, 0, 0, 0x3b, 0, 0, 0, 0x40, 0, 0, 0, 0x10, 0, 0, 0],
+        );
+    root.child("sram@10000000")
+        .property("reg", &[0x10, 0, 0, 0, 0, 1, 0, 0]);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(
+        dt.memory_regions(),
+        vec![
+            MemoryRegion {
+                base: 0,
+                size: 0x3b00_0000,
+            },
+            MemoryRegion {
+                base: 0x4000_0000,
+                size: 0x1000_0000,
+            },
+        ]
+    );
+}
+
+#[test]
+fn memory_regions_64bit() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 2)
+        .property_u32("#size-cells", 2);
+    root.child("memory@80000000")
+        .property_str("device_type", "memory")
+        .property("reg", &[0, 0, 0, 0, 0x80, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0]);
+    root.child("memory@880000000")
+        .property_str("device_type", "memory")
+        .property("reg", &[0, 0, 0, 8, 0x80, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0]);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(
+        dt.memory_regions(),
+        vec![
+            MemoryRegion {
+                base: 0x8000_0000,
+                size: 0x1_0000_0000,
+            },
+            MemoryRegion {
+                base: 0x8_8000_0000,
+                size: 0x2_0000_0000,
+            },
+        ]
+    );
+}
+
+#[test]
+fn rpi_memory_regions() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    // the firmware fills in the size at boot
+    assert_eq!(dt.memory_regions(), vec![MemoryRegion { base: 0, size: 0 }]);
+}
+
+#[test]
+fn region_bounds() {
+    let region = MemoryRegion {
+        base: 0x8000_0000,
+        size: 0x1000,
+    };
+
+    assert_eq!(region.end(), 0x8000_1000);
+    assert!(region.contains(0x8000_0000));
+    assert!(region.contains(0x8000_0fff));
+    assert!(!region.contains(0x8000_1000));
+    assert!(!region.contains(0x7fff_ffff));
+
+    let top = MemoryRegion {
+        base: u64::MAX - 1,
+        size: 0x10,
+    };
+    assert_eq!(top.end(), u64::MAX);
+    assert!(top.contains(u64::MAX));
+}
+
+#[test]
+fn memory_skips_disabled_nodes() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1);
+    root.child("memory@0")
+        .property_str("device_type", "memory")
+        .property("reg", &[0, 0, 0, 0, 0x40, 0, 0, 0]);
+    root.child("memory@80000000")
+        .property_str("device_type", "memory")
+        .property_str("status", "disabled")
+        .property("reg", &[0x80, 0, 0, 0, 0x40, 0, 0, 0]);
+    root.child("memory@c0000000")
+        .property_str("device_type", "memory")
+        .property_str("status", "okay")
+        .property("reg", &[0xc0, 0, 0, 0, 0x10, 0, 0, 0]);
+    let dt = builder.build().unwrap();
+
+    let bases: Vec<u64> = dt.memory().map(|region| region.base).collect();
+    assert_eq!(bases, [0, 0xc000_0000]);
+    assert_eq!(dt.memory_regions().len(), 2);
+}
+
+#[test]
+fn reserved_memory_children() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 2)
+        .property_u32("#size-cells", 2);
+    let mut reserved = root.child("reserved-memory");
+    // the children use these, not the root's cell counts
+    reserved
+        .property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1)
+        .property("ranges", &[]);
+    reserved
+        .child("secmon@3e000000")
+        .property("reg", &[0x3e, 0, 0, 0, 0, 0x10, 0, 0])
+        .property("no-map", &[]);
+    reserved
+        .child("linux,cma")
+        .property_str("compatible", "shared-dma-pool")
+        .property_u32("size", 0x0400_0000)
+        .property_u32("alignment", 0x0040_0000)
+        .property("alloc-ranges", &[0, 0, 0, 0, 0x40, 0, 0, 0])
+        .property("reusable", &[]);
+    reserved
+        .child("unused@0")
+        .property_str("status", "disabled")
+        .property("reg", &[0, 0, 0, 0, 0, 0x10, 0, 0]);
+    let dt = builder.build().unwrap();
+
+    let entries: Vec<_> = dt.reserved_memory().collect();
+    assert_eq!(
+        entries,
+        [
+            ReservedMemory {
+                name: "secmon@3e000000",
+                regions: vec![MemoryRegion {
+                    base: 0x3e00_0000,
+                    size: 0x10_0000,
+                }],
+                size: None,
+                alignment: None,
+                alloc_ranges: Vec::new(),
+                no_map: true,
+                reusable: false,
+            },
+            ReservedMemory {
+                name: "linux,cma",
+                regions: Vec::new(),
+                size: Some(0x0400_0000),
+                alignment: Some(0x0040_0000),
+                alloc_ranges: vec![MemoryRegion {
+                    base: 0,
+                    size: 0x4000_0000,
+                }],
+                no_map: false,
+                reusable: true,
+            },
+        ]
+    );
+}
+
+#[test]
+fn reserved_memory_malformed() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    let mut reserved = root.child("reserved-memory");
+    reserved
+        .property_u32("#address-cells", 2)
+        .property_u32("#size-cells", 2);
+    // a single cell where #size-cells asks for two
+    reserved.child("pool").property_u32("size", 0x1000);
+    let dt = builder.build().unwrap();
+
+    let pool = dt.reserved_memory().next().unwrap();
+    assert_eq!(pool.size, None);
+    assert!(pool.regions.is_empty());
+
+    let dt = DeviceTree::load(DTB).unwrap();
+    assert_eq!(dt.reserved_memory().count(), 0);
+}
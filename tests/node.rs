@@ -0,0 +1,333 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn property_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let model = dt.root.property("model").unwrap();
+    assert_eq!(model.name(), "model");
+    assert_eq!(model.data(), b"Raspberry Pi 2 Model B\0");
+    assert!(dt.root.has_property("compatible"));
+
+    assert!(dt.root.property("serial-number").is_none());
+    assert!(!dt.root.has_property("serial-number"));
+}
+
+#[test]
+fn empty_property_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let gpio = dt.find("/soc/gpio@7e200000").unwrap();
+
+    let prop = gpio.property("gpio-controller").unwrap();
+    assert!(prop.data().is_empty());
+    assert!(gpio.has_property("gpio-controller"));
+}
+
+#[test]
+fn child_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let cpus = dt.find("/cpus").unwrap();
+
+    assert_eq!(cpus.child_count(), 4);
+    assert_eq!(cpus.child(0).unwrap().name(), "cpu@0");
+    assert_eq!(cpus.child(3).unwrap().name(), "cpu@3");
+    assert!(cpus.child(4).is_none());
+
+    assert_eq!(cpus.find_child("cpu@2").unwrap().name(), "cpu@2");
+    assert!(cpus.find_child("cpu").is_none());
+    assert!(cpus.find_child("cpu@4").is_none());
+
+    assert_eq!(cpus.find_child_by_base_name("cpu").unwrap().name(), "cpu@0");
+    assert!(cpus.find_child_by_base_name("cpu@1").is_none());
+    assert_eq!(
+        dt.root.find_child_by_base_name("memory").unwrap().name(),
+        "memory"
+    );
+}
+
+fn node(name: &str, children: Vec<Node>) -> Node {
+    let mut node = Node::new(name);
+    for child in children {
+        node.add_child(child).unwrap();
+    }
+    node
+}
+
+#[test]
+fn children_by_base_name() {
+    let soc = node(
+        "soc",
+        vec![
+            node("mmc@1000", vec![]),
+            node("serial", vec![]),
+            node("mmc", vec![]),
+            node("mmcx@3000", vec![]),
+            node("mmc@2000", vec![]),
+        ],
+    );
+
+    let names: Vec<_> = soc.children_by_name("mmc").map(|n| n.name()).collect();
+    assert_eq!(names, ["mmc@1000", "mmc", "mmc@2000"]);
+    assert_eq!(soc.children_by_name("ethernet").count(), 0);
+    assert_eq!(soc.children_by_name("mmc@1000").count(), 0);
+
+    let dt = DeviceTree::load(DTB).unwrap();
+    let cpus = dt.find("/cpus").unwrap();
+    assert_eq!(cpus.children_by_name("cpu").count(), 4);
+}
+
+#[test]
+fn property_mut_lookup() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+
+    let model = dt.root.property_mut("model").unwrap();
+    model.set_data(b"Raspberry Pi\0".to_vec());
+    assert_eq!(dt.root.prop_str("model"), Ok("Raspberry Pi"));
+
+    assert!(dt.root.property_mut("serial-number").is_none());
+    // properties of other nodes are not found on the root
+    assert!(dt.root.property_mut("bootargs").is_none());
+}
+
+#[test]
+fn property_u32_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.root.property_u32("#address-cells"), Some(1));
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(uart.property_u32("phandle"), Some(0x17));
+
+    // reg holds two cells here
+    assert_eq!(uart.property_u32("reg"), None);
+    assert_eq!(uart.property_u32("absent"), None);
+}
+
+#[test]
+fn property_str_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(
+        dt.root.property_str("model"),
+        Some("Raspberry Pi 2 Model B")
+    );
+    let uart = dt.find("/soc/uart@7e215040").unwrap();
+    assert_eq!(
+        uart.property_str("compatible"),
+        Some("brcm,bcm2835-aux-uart")
+    );
+    assert_eq!(uart.property_str("status"), Some("disabled"));
+    assert_eq!(uart.property_str("absent"), None);
+}
+
+#[test]
+fn flag_properties() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let intc = dt.find("/soc/interrupt-controller@7e00b200").unwrap();
+
+    assert!(intc.property("interrupt-controller").unwrap().is_empty());
+    assert!(intc.has_flag("interrupt-controller"));
+    assert!(intc.has_property("interrupt-controller"));
+
+    // present, but not empty
+    assert!(!intc.property("compatible").unwrap().is_empty());
+    assert!(!intc.has_flag("compatible"));
+    assert!(intc.has_property("compatible"));
+
+    assert!(!intc.has_flag("dma-coherent"));
+}
+
+fn empty_node(name: &str) -> Node {
+    Node::new(name)
+}
+
+#[test]
+fn set_and_remove_property() {
+    let mut node = empty_node("uart@1000");
+
+    node.set_property("status", b"okay\0".to_vec()).unwrap();
+    node.set_property("reg", vec![0, 0, 0x10, 0]).unwrap();
+    node.set_property("status", b"disabled\0".to_vec()).unwrap();
+
+    assert_eq!(node.properties().len(), 2);
+    assert_eq!(node.property_str("status"), Some("disabled"));
+
+    assert!(node.remove_property("status"));
+    assert!(!node.remove_property("status"));
+    assert_eq!(node.properties().len(), 1);
+    assert!(node.has_property("reg"));
+}
+
+#[test]
+fn set_property_rejects_invalid_names() {
+    let mut node = empty_node("uart@1000");
+
+    for name in ["", "with space", "nul\0", "ünicode"] {
+        assert_eq!(
+            node.set_property(name, Vec::new()),
+            Err(NodeError::InvalidPropertyName)
+        );
+    }
+    assert!(node.properties().is_empty());
+}
+
+#[test]
+fn add_and_remove_child() {
+    let mut node = empty_node("");
+
+    node.add_child(empty_node("cpus")).unwrap();
+    node.add_child(empty_node("memory@0")).unwrap();
+    assert_eq!(node.child_count(), 2);
+
+    assert_eq!(node.remove_child("memory@0"), Some(empty_node("memory@0")));
+    assert_eq!(node.remove_child("memory@0"), None);
+    assert_eq!(node.child_count(), 1);
+}
+
+#[test]
+fn add_child_rejects_invalid_names() {
+    let mut node = empty_node("");
+
+    assert_eq!(
+        node.add_child(empty_node("bad name")),
+        Err(NodeError::InvalidNodeName)
+    );
+
+    let mut child = empty_node("soc");
+    child.set_property("status", Vec::new()).unwrap();
+    child.properties_mut()[0] = Property::new("bad\0name", Vec::new());
+    assert_eq!(node.add_child(child), Err(NodeError::InvalidPropertyName));
+    assert!(node.children().is_empty());
+}
+
+fn with_status(status: &[u8]) -> Node {
+    let mut node = empty_node("uart@1000");
+    node.set_property("status", status.to_vec()).unwrap();
+    node
+}
+
+#[test]
+fn node_status() {
+    assert_eq!(empty_node("uart@1000").status(), NodeStatus::Okay);
+    assert_eq!(with_status(b"okay\0").status(), NodeStatus::Okay);
+    assert_eq!(with_status(b"ok\0").status(), NodeStatus::Okay);
+    assert_eq!(with_status(b"disabled\0").status(), NodeStatus::Disabled);
+    assert_eq!(with_status(b"reserved\0").status(), NodeStatus::Reserved);
+    assert_eq!(
+        with_status(b"fail\0").status(),
+        NodeStatus::Fail(String::new())
+    );
+    assert_eq!(
+        with_status(b"fail-overheated\0").status(),
+        NodeStatus::Fail("overheated".to_owned())
+    );
+    assert_eq!(
+        with_status(b"broken\0").status(),
+        NodeStatus::Unknown(b"broken\0".to_vec())
+    );
+    assert_eq!(
+        with_status(b"okay").status(),
+        NodeStatus::Unknown(b"okay".to_vec())
+    );
+}
+
+#[test]
+fn node_is_enabled() {
+    assert!(empty_node("uart@1000").is_enabled());
+    assert!(with_status(b"okay\0").is_enabled());
+    assert!(with_status(b"ok\0").is_enabled());
+
+    for status in [&b"disabled\0"[..], b"reserved\0", b"fail\0", b"fail-x\0"] {
+        assert!(!with_status(status).is_enabled());
+    }
+    assert!(!with_status(b"\xff\0").is_enabled());
+
+    let dt = DeviceTree::load(DTB).unwrap();
+    assert!(dt.find("/soc/uart@7e201000").unwrap().is_enabled());
+    assert!(!dt.find("/soc/uart@7e215040").unwrap().is_enabled());
+}
+
+#[test]
+fn name_parts() {
+    let cpu = empty_node("cpu@0");
+    assert_eq!(cpu.base_name(), "cpu");
+    assert_eq!(cpu.unit_address(), Some("0"));
+
+    let uart = empty_node("uart@ff000000");
+    assert_eq!(uart.base_name(), "uart");
+    assert_eq!(uart.unit_address(), Some("ff000000"));
+
+    let cpus = empty_node("cpus");
+    assert_eq!(cpus.base_name(), "cpus");
+    assert_eq!(cpus.unit_address(), None);
+
+    let root = empty_node("");
+    assert_eq!(root.base_name(), "");
+    assert_eq!(root.unit_address(), None);
+}
+
+#[test]
+fn compatible_entries() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+
+    let compat: Vec<_> = uart.compatible().collect();
+    assert_eq!(compat, ["arm,pl011", "arm,primecell"]);
+    assert!(uart.is_compatible("arm,primecell"));
+    assert!(!uart.is_compatible("arm,pl01"));
+
+    let mut node = empty_node("uart@1000");
+    assert_eq!(node.compatible().count(), 0);
+    assert!(!node.is_compatible(""));
+
+    node.set_property("compatible", b"ns16550a\0\0ns8250\0".to_vec())
+        .unwrap();
+    let compat: Vec<_> = node.compatible().collect();
+    assert_eq!(compat, ["ns16550a", "ns8250"]);
+    assert!(!node.is_compatible(""));
+}
+
+#[test]
+fn iterate_over_children() {
+    let dt =
+        DeviceTree::load(include_bytes!("../examples/bcm2709-rpi-2-b.dtb"))
+            .unwrap();
+    let cpus = dt.find("/cpus").unwrap();
+
+    let mut names = Vec::new();
+    for cpu in cpus {
+        names.push(cpu.name());
+    }
+    assert_eq!(names, ["cpu@0", "cpu@1", "cpu@2", "cpu@3"]);
+    assert!(cpus.into_iter().eq(cpus.children()));
+    assert_eq!(cpus.properties()[0].name(), "#address-cells");
+}
+
+#[test]
+fn modify_in_place() {
+    let mut node = node("soc", vec![empty_node("uart@1000")]);
+    node.set_property("ranges", Vec::new()).unwrap();
+
+    node.properties_mut()[0].set_data(vec![0, 0, 0, 1]);
+    node.children_mut()[0].set_name("serial@1000").unwrap();
+
+    assert_eq!(node.property_u32("ranges"), Some(1));
+    assert!(node.find_child("serial@1000").is_some());
+    assert_eq!(node.clone(), node);
+}
+
+#[test]
+fn rename_checks_name() {
+    let mut node = empty_node("uart@1000");
+
+    assert_eq!(node.set_name("uart#1"), Err(NodeError::InvalidNodeName));
+    assert_eq!(node.set_name(""), Err(NodeError::InvalidNodeName));
+    assert_eq!(node.name(), "uart@1000");
+
+    node.set_name("serial@1000").unwrap();
+    assert_eq!(node.name(), "serial@1000");
+    assert_eq!(node.base_name(), "serial");
+}
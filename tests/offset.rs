@@ -0,0 +1,91 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+fn be_u32(offset: usize) -> u32 {
+    u32::from_be_bytes(DTB[offset..offset + 4].try_into().unwrap())
+}
+
+#[test]
+fn node_offsets() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.root.struct_offset(), Some(dt.off_dt_struct() as usize));
+    for node in dt.iter() {
+        let offset = node.struct_offset().unwrap();
+        // FDT_BEGIN_NODE, followed by the NUL-terminated name
+        assert_eq!(be_u32(offset), 1);
+        let name = &DTB[offset + 4..offset + 4 + node.name().len() + 1];
+        assert_eq!(&name[..node.name().len()], node.name().as_bytes());
+        assert_eq!(name[node.name().len()], 0);
+    }
+
+    let soc = dt.find("/soc").unwrap().struct_offset().unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    assert!(uart.struct_offset().unwrap() > soc);
+}
+
+#[test]
+fn property_offsets() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let mut count = 0;
+    for (path, prop) in dt.properties_with_paths() {
+        let offset = prop.data_offset().unwrap();
+        assert_eq!(
+            &DTB[offset..offset + prop.data().len()],
+            prop.data(),
+            "{}",
+            path
+        );
+        // FDT_PROP and the length of the value come first
+        assert_eq!(be_u32(offset - 12), 3);
+        assert_eq!(be_u32(offset - 8) as usize, prop.data().len());
+        count += 1;
+    }
+    assert_eq!(count, 439);
+
+    let model = dt.root.property("model").unwrap();
+    let offset = model.data_offset().unwrap();
+    assert!(DTB[offset..].starts_with(b"Raspberry Pi 2 Model B\0"));
+}
+
+#[test]
+fn borrowed_offsets() {
+    let borrowed = BorrowedTree::load(DTB).unwrap();
+    let dt = borrowed.to_tree();
+
+    let uart = borrowed.find("/soc/uart@7e201000").unwrap();
+    let owned = dt.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(owned.struct_offset(), Some(uart.struct_offset()));
+
+    let status = uart.property("status").unwrap();
+    assert_eq!(&DTB[status.data_offset()..][..5], b"okay\0");
+    assert_eq!(
+        owned.property("status").unwrap().data_offset(),
+        Some(status.data_offset())
+    );
+}
+
+#[test]
+fn offsets_do_not_affect_equality() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    // storing the tree moves everything after the header
+    let stored = dt.store().unwrap();
+    let reloaded = DeviceTree::load(&stored).unwrap();
+    assert_eq!(reloaded, dt);
+
+    let prop = dt.root.property("model").unwrap();
+    let built = Property::new("model", prop.data().to_vec());
+    assert_eq!(built.data_offset(), None);
+    assert_eq!(&built, prop);
+
+    let mut builder = DeviceTreeBuilder::new();
+    builder.root("").child("chosen");
+    let dt = builder.build().unwrap();
+    assert!(dt.iter().all(|node| node.struct_offset().is_none()));
+    assert_eq!(Node::new("chosen").struct_offset(), None);
+}
@@ -0,0 +1,166 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+fn base() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("aliases")
+        .property_str("serial0", "/soc/uart@1000");
+    root.child("__symbols__")
+        .property_str("uart0", "/soc/uart@1000")
+        .property_str("intc", "/soc/intc@0");
+    let mut soc = root.child("soc");
+    soc.child("intc@0").property_u32("phandle", 1);
+    soc.child("uart@1000")
+        .property_u32("phandle", 2)
+        .property_str("status", "disabled");
+    builder.build().unwrap()
+}
+
+/// What `dtc -@` makes of an overlay adding a clock and a bluetooth device
+/// to `&uart0` and a property to the root node.
+fn overlay() -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+
+    let mut fragment = root.child("fragment@0");
+    fragment.property_u32("target", 0xffff_ffff);
+    let mut contents = fragment.child("__overlay__");
+    contents.property_str("status", "okay");
+    contents.child("clk").property_u32("phandle", 1);
+    contents
+        .child("bt")
+        .property("clocks", &[0, 0, 0, 1, 0, 0, 0, 7])
+        .property_u32("interrupt-parent", 0xffff_ffff);
+
+    root.child("fragment@1")
+        .property_str("target-path", "/")
+        .child("__overlay__")
+        .property_str("model", "overlaid");
+
+    root.child("__symbols__")
+        .property_str("btclk", "/fragment@0/__overlay__/clk")
+        .property_str("top", "/fragment@1/__overlay__");
+    root.child("__fixups__")
+        .property_str("uart0", "/fragment@0:target:0")
+        .property_str("intc", "/fragment@0/__overlay__/bt:interrupt-parent:0");
+    root.child("__local_fixups__")
+        .child("fragment@0")
+        .child("__overlay__")
+        .child("bt")
+        .property_u32("clocks", 0);
+
+    builder.build().unwrap()
+}
+
+#[test]
+fn apply_fragments() {
+    let base = base();
+    let dt = base.apply_overlay(&overlay()).unwrap();
+
+    let uart = dt.find("/soc/uart@1000").unwrap();
+    assert_eq!(uart.property_str("status"), Some("okay"));
+    assert_eq!(uart.property_u32("phandle"), Some(2));
+
+    // overlay phandles are moved past the highest one of the base
+    let clk = uart.find_child("clk").unwrap();
+    assert_eq!(clk.property_u32("phandle"), Some(3));
+    let bt = uart.find_child("bt").unwrap();
+    assert_eq!(
+        bt.property("clocks").unwrap().as_u32_array(),
+        Some(vec![3, 7])
+    );
+    assert_eq!(bt.property_u32("interrupt-parent"), Some(1));
+
+    assert_eq!(dt.root.property_str("model"), Some("overlaid"));
+
+    let symbols = dt.find("/__symbols__").unwrap();
+    assert_eq!(symbols.property_str("uart0"), Some("/soc/uart@1000"));
+    assert_eq!(symbols.property_str("btclk"), Some("/soc/uart@1000/clk"));
+    assert_eq!(symbols.property_str("top"), Some("/"));
+
+    // the base tree is left alone
+    assert_eq!(base, self::base());
+}
+
+#[test]
+fn target_path_through_alias() {
+    let mut builder = DeviceTreeBuilder::new();
+    builder
+        .root("")
+        .child("fragment@0")
+        .property_str("target-path", "serial0")
+        .child("__overlay__")
+        .property_str("status", "okay");
+    let overlay = builder.build().unwrap();
+
+    let dt = base().apply_overlay(&overlay).unwrap();
+    let uart = dt.find("/soc/uart@1000").unwrap();
+    assert_eq!(uart.property_str("status"), Some("okay"));
+}
+
+fn single_fragment(target: Option<&str>) -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    let mut fragment = root.child("fragment@0");
+    if let Some(path) = target {
+        fragment.property_str("target-path", path);
+    }
+    fragment.child("__overlay__");
+    builder.build().unwrap()
+}
+
+#[test]
+fn overlay_errors() {
+    let base = base();
+
+    assert_eq!(
+        base.apply_overlay(&single_fragment(None)),
+        Err(OverlayError::MissingTarget)
+    );
+    assert_eq!(
+        base.apply_overlay(&single_fragment(Some("/soc/spi@2000"))),
+        Err(OverlayError::TargetNotFound)
+    );
+
+    let mut overlay = single_fragment(Some("/soc"));
+    let mut fixups = Node::new("__fixups__");
+    fixups
+        .set_property("gpio", b"/fragment@0:target:0\0".to_vec())
+        .unwrap();
+    overlay.root.add_child(fixups.clone()).unwrap();
+    assert_eq!(
+        base.apply_overlay(&overlay),
+        Err(OverlayError::UnknownSymbol)
+    );
+
+    fixups
+        .set_property("uart0", b"/fragment@0:target\0".to_vec())
+        .unwrap();
+    fixups.remove_property("gpio");
+    overlay.root.remove_child("__fixups__");
+    overlay.root.add_child(fixups).unwrap();
+    assert_eq!(
+        base.apply_overlay(&overlay),
+        Err(OverlayError::InvalidFixup)
+    );
+}
+
+#[test]
+fn phandles_out_of_range() {
+    let mut builder = DeviceTreeBuilder::new();
+    builder
+        .root("")
+        .child("fragment@0")
+        .property_str("target-path", "/")
+        .child("__overlay__")
+        .child("clk")
+        .property_u32("phandle", 0xffff_fffe);
+    let overlay = builder.build().unwrap();
+
+    assert_eq!(
+        base().apply_overlay(&overlay),
+        Err(OverlayError::PhandleConflict)
+    );
+}
@@ -0,0 +1,144 @@
+extern crate flat_device_tree;
+
+mod common;
+
+use common::*;
+use flat_device_tree::*;
+
+#[test]
+fn skips_nop_tokens() {
+    let structure = Structure::default()
+        .token(NOP)
+        .begin_node("")
+        .token(NOP)
+        .prop(0, &[0, 0, 0, 1])
+        .token(NOP)
+        .token(NOP)
+        .prop(5, b"nop\0")
+        .token(NOP)
+        .begin_node("child")
+        .token(NOP)
+        .token(END_NODE)
+        .token(NOP)
+        .token(END_NODE)
+        .token(END);
+    let dtb = blob(&structure.0, b"cell\0name\0");
+
+    let dt = DeviceTree::load(&dtb).unwrap();
+
+    assert_eq!(dt.root.properties().len(), 2);
+    assert_eq!(dt.root.prop_u32("cell"), Ok(1));
+    assert_eq!(dt.root.prop_str("name"), Ok("nop"));
+    assert_eq!(dt.root.children().len(), 1);
+    assert_eq!(dt.root.children()[0].name(), "child");
+}
+
+#[test]
+fn empty_property_at_end_of_structure() {
+    let structure = Structure::default()
+        .begin_node("")
+        .prop(0, &[0, 0, 0, 1])
+        .prop(5, &[])
+        .begin_node("child")
+        .prop(0, &[0, 0, 0, 2])
+        .prop(5, &[])
+        .token(END_NODE)
+        .token(END_NODE)
+        .token(END);
+    let dtb = blob(&structure.0, b"cell\0flag\0");
+
+    let dt = DeviceTree::load(&dtb).unwrap();
+
+    assert!(dt.root.has_flag("flag"));
+    assert!(dt.root.children()[0].has_flag("flag"));
+}
+
+#[test]
+fn errors_report_position() {
+    // the root node is never closed
+    let structure = Structure::default().begin_node("");
+    let dtb = blob(&structure.0, b"");
+    let end = 56 + structure.0.len();
+
+    assert_eq!(
+        DeviceTree::load(&dtb),
+        Err(Error::SliceReadError(SliceReadError::UnexpectedEndOfInput(
+            end
+        )))
+    );
+
+    // a property token where a node should start
+    let structure = Structure::default().prop(0, &[]).token(END);
+    let dtb = blob(&structure.0, b"cell\0");
+
+    assert_eq!(DeviceTree::load(&dtb), Err(Error::ParseError(56)));
+
+    // an unknown token in place of the end of the root node
+    let structure = Structure::default().begin_node("").token(7).token(END);
+    let dtb = blob(&structure.0, b"");
+
+    assert_eq!(DeviceTree::load(&dtb), Err(Error::ParseError(56 + 8)));
+}
+
+#[test]
+fn header_blocks_must_fit() {
+    let structure = Structure::default()
+        .begin_node("")
+        .prop(0, &[0, 0, 0, 1])
+        .token(END_NODE)
+        .token(END);
+    let dtb = blob(&structure.0, b"cell\0");
+    assert!(DeviceTree::load(&dtb).is_ok());
+
+    let patched = |offset: usize, value: u32| {
+        let mut dtb = dtb.clone();
+        dtb[offset..offset + 4].copy_from_slice(&value.to_be_bytes());
+        DeviceTree::load(&dtb)
+    };
+
+    // off_dt_struct, off_dt_strings and off_mem_rsvmap
+    assert_eq!(patched(8, 0xffff_ffff), Err(Error::InvalidHeader));
+    assert_eq!(patched(12, dtb.len() as u32 - 4), Err(Error::InvalidHeader));
+    assert_eq!(patched(12, dtb.len() as u32), Err(Error::InvalidHeader));
+    assert_eq!(patched(16, dtb.len() as u32), Err(Error::InvalidHeader));
+    // size_dt_strings and size_dt_struct
+    assert_eq!(patched(32, 6), Err(Error::InvalidHeader));
+    assert_eq!(patched(36, 0x1000), Err(Error::InvalidHeader));
+
+    // an empty strings block may end the blob, but not start past its end
+    let empty_strings = |offset: u32| {
+        let mut dtb = dtb.clone();
+        dtb[12..16].copy_from_slice(&offset.to_be_bytes());
+        dtb[32..36].copy_from_slice(&0u32.to_be_bytes());
+        DeviceTree::load(&dtb).map(|_| ())
+    };
+    assert_eq!(
+        empty_strings(dtb.len() as u32 + 1),
+        Err(Error::InvalidHeader)
+    );
+    assert_eq!(empty_strings(0xffff_ffff), Err(Error::InvalidHeader));
+
+    // as in the blob of a tree without properties
+    let dt = DeviceTree::new(Node::new(""));
+    assert_eq!(DeviceTree::load(&dt.store().unwrap()), Ok(dt));
+}
+
+#[test]
+fn unterminated_memory_reservations() {
+    let structure = Structure::default()
+        .begin_node("")
+        .prop(0, &[0, 0, 0, 1])
+        .token(END_NODE)
+        .token(END);
+    let mut dtb = blob(&structure.0, b"cell\0");
+    // the last entry would start 8 bytes before the end of the blob
+    let off_mem_rsvmap = dtb.len() as u32 - 8;
+    dtb[16..20].copy_from_slice(&off_mem_rsvmap.to_be_bytes());
+
+    assert_eq!(
+        DeviceTree::load(&dtb),
+        Err(Error::SliceReadError(SliceReadError::UnexpectedEndOfInput(
+            dtb.len()
+        )))
+    );
+}
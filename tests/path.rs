@@ -0,0 +1,178 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn node_paths() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    for path in ["/", "/soc", "/soc/uart@7e201000", "/clocks/clock@5"] {
+        let node = dt.find(path).unwrap();
+        assert_eq!(dt.node_path(node).as_deref(), Some(path));
+    }
+}
+
+#[test]
+fn node_paths_by_identity() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let other = DeviceTree::load(DTB).unwrap();
+
+    // equal, but not part of `dt`
+    let uart = other.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(dt.node_path(uart), None);
+}
+
+#[test]
+fn node_paths_for_every_node() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    for node in dt.iter_dfs() {
+        let path = dt.node_path(node).unwrap();
+        assert!(std::ptr::eq(dt.find(&path).unwrap(), node), "{}", path);
+    }
+}
+
+#[test]
+fn node_paths_of_found_nodes() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let other = DeviceTree::load(DTB).unwrap();
+
+    let paths: Vec<_> = dt
+        .find_compatible("fixed-clock")
+        .map(|node| dt.node_path(node).unwrap())
+        .collect();
+    assert_eq!(paths[0], "/clocks/clock@0");
+    assert!(paths.iter().all(|path| path.starts_with("/clocks/clock@")));
+
+    let intc = dt.iter().find(|n| n.has_flag("interrupt-controller"));
+    assert_eq!(
+        dt.node_path(intc.unwrap()).unwrap(),
+        "/soc/interrupt-controller@7e00b200"
+    );
+
+    let root = dt.find("/").unwrap();
+    assert_eq!(dt.node_path(root).unwrap(), "/");
+    let uart = other.find_compatible("arm,pl011").next().unwrap();
+    assert_eq!(dt.node_path(uart), None);
+}
+
+#[test]
+fn display_node_ref() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let other = DeviceTree::load(DTB).unwrap();
+
+    let clock = dt.find("/clocks/clock@0").unwrap();
+    assert_eq!(NodeRef::new(&dt, clock).to_string(), "/clocks/clock@0");
+    assert_eq!(NodeRef::new(&dt, &dt.root).to_string(), "/");
+
+    let clock = other.find("/clocks/clock@0").unwrap();
+    assert_eq!(NodeRef::new(&dt, clock).to_string(), "clock@0");
+}
+
+#[test]
+fn parse_node_paths() {
+    for path in ["/", "/soc", "/soc/uart@7e201000", "/__symbols__"] {
+        assert_eq!(NodePath::parse(path).unwrap().as_str(), path);
+    }
+
+    assert_eq!(NodePath::parse(""), Err(PathError::NotAbsolute));
+    assert_eq!(NodePath::parse("soc/uart"), Err(PathError::NotAbsolute));
+    assert_eq!(NodePath::parse("//soc"), Err(PathError::EmptyComponent));
+    assert_eq!(
+        NodePath::parse("/soc//uart"),
+        Err(PathError::EmptyComponent)
+    );
+    assert_eq!(NodePath::parse("/soc/"), Err(PathError::EmptyComponent));
+    assert_eq!(
+        NodePath::parse("/soc/uart@"),
+        Err(PathError::InvalidComponent)
+    );
+    assert_eq!(NodePath::parse("/so c"), Err(PathError::InvalidComponent));
+    assert_eq!(NodePath::parse("/@1000"), Err(PathError::InvalidComponent));
+}
+
+#[test]
+fn node_path_components() {
+    let path = NodePath::parse("/soc/gpio@7e200000/i2c0").unwrap();
+
+    let components: Vec<_> = path.components().collect();
+    assert_eq!(components, ["soc", "gpio@7e200000", "i2c0"]);
+    assert_eq!(NodePath::root().components().count(), 0);
+
+    let parent = path.parent().unwrap();
+    assert_eq!(parent.to_string(), "/soc/gpio@7e200000");
+    let parent = parent.parent().unwrap();
+    assert_eq!(parent, NodePath::parse("/soc").unwrap());
+    let parent = parent.parent().unwrap();
+    assert_eq!(parent, NodePath::root());
+    assert_eq!(parent.parent(), None);
+}
+
+#[test]
+fn push_node_path() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let soc = NodePath::root().push("soc").unwrap();
+    let uart = soc.push("uart@7e201000").unwrap();
+    assert_eq!(uart.as_str(), "/soc/uart@7e201000");
+    assert!(dt.find(uart.as_str()).is_some());
+
+    assert_eq!(soc.push(""), Err(PathError::EmptyComponent));
+    assert_eq!(soc.push("a/b"), Err(PathError::InvalidComponent));
+    assert_eq!(soc.push("uart@"), Err(PathError::InvalidComponent));
+
+    // every node of the fixture has a valid path
+    for (path, _) in dt.iter_with_paths() {
+        let parsed = NodePath::parse(&path).unwrap();
+        assert_eq!(parsed.to_string(), path);
+    }
+}
+
+#[test]
+fn get_by_spec() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let model = dt.get("/:model").unwrap().property().unwrap();
+    assert_eq!(model.as_str(), Some("Raspberry Pi 2 Model B"));
+    let clocks = dt.get("/soc/uart@7e201000:clocks").unwrap();
+    assert_eq!(
+        clocks.property().unwrap().cells().collect::<Vec<_>>(),
+        [3, 4]
+    );
+
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(dt.get("/soc/uart@7e201000"), Some(TreeEntry::Node(uart)));
+    assert_eq!(dt.get("/soc/uart@7e201000/"), Some(TreeEntry::Node(uart)));
+    assert!(dt.get("/").unwrap().node().unwrap().name().is_empty());
+
+    assert_eq!(dt.get("/soc/uart@7e201000:bogus"), None);
+    assert_eq!(dt.get("/soc/uart@7e201000:"), None);
+    assert_eq!(dt.get("/soc/bogus:status"), None);
+    assert_eq!(dt.get(""), None);
+}
+
+#[test]
+fn get_by_spec_with_alias() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("aliases")
+        .property_str("serial0", "/soc/serial@10000000");
+    root.child("soc")
+        .child("serial@10000000")
+        .property_u32("clock-frequency", 48_000_000)
+        // the spec is split on the first ':', wherever values have them
+        .property_str("label", "uart:0");
+    let dt = builder.build().unwrap();
+
+    let freq = dt.get("serial0:clock-frequency").unwrap();
+    assert_eq!(freq.property().unwrap().as_u32(), Some(48_000_000));
+    let label = dt.get("/soc/serial@10000000:label").unwrap();
+    assert_eq!(label.property().unwrap().as_str(), Some("uart:0"));
+
+    let serial = dt.get("serial0").unwrap().node().unwrap();
+    assert_eq!(serial.name(), "serial@10000000");
+    assert!(dt.get("serial0").unwrap().property().is_none());
+    assert_eq!(dt.get("serial1:clock-frequency"), None);
+}
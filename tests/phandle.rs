@@ -0,0 +1,495 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn phandle_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.find_by_phandle(0x17).unwrap().name(), "uart@7e201000");
+    assert_eq!(dt.find_by_phandle(0x24).unwrap().name(), "soc");
+    assert!(dt.find_by_phandle(0xffff).is_none());
+}
+
+#[test]
+fn legacy_phandle_lookup() {
+    let mut intc = Node::new("intc");
+    intc.set_property("linux,phandle", vec![0, 0, 0, 7])
+        .unwrap();
+    let mut root = Node::new("");
+    root.add_child(intc).unwrap();
+    let dt = DeviceTree::new(root);
+
+    assert_eq!(dt.find_by_phandle(7).unwrap().name(), "intc");
+    assert_eq!(dt.build_phandle_index().get(7).unwrap().as_str(), "/intc");
+}
+
+#[test]
+fn node_phandles() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(uart.phandle_value(), Some(0x17));
+    assert_eq!(dt.find("/chosen").unwrap().phandle_value(), None);
+
+    let mut node = Node::new("intc");
+    node.set_property("linux,phandle", vec![0, 0, 0, 7])
+        .unwrap();
+    assert_eq!(node.phandle_value(), Some(7));
+    node.set_property("phandle", vec![0, 0, 0, 8]).unwrap();
+    assert_eq!(node.phandle_value(), Some(8));
+
+    node.set_phandle(9);
+    assert_eq!(node.phandle_value(), Some(9));
+    assert_eq!(node.property_u32("phandle"), Some(9));
+    assert!(!node.has_property("linux,phandle"));
+    assert_eq!(node.properties().len(), 1);
+}
+
+#[test]
+fn cross_referenced_phandles() {
+    let mut intc = Node::new("interrupt-controller@1000");
+    intc.set_property("interrupt-controller", Vec::new())
+        .unwrap();
+    intc.set_phandle(1);
+    let mut clock = Node::new("clock");
+    clock.set_phandle(2);
+    let mut uart = Node::new("uart@2000");
+    uart.set_property("interrupt-parent", vec![0, 0, 0, 1])
+        .unwrap();
+    uart.set_property("clocks", vec![0, 0, 0, 2]).unwrap();
+    uart.set_phandle(3);
+
+    let mut root = Node::new("");
+    root.add_child(intc).unwrap();
+    root.add_child(clock).unwrap();
+    root.add_child(uart).unwrap();
+    let dt = DeviceTree::load(&DeviceTree::new(root).store().unwrap()).unwrap();
+
+    let uart = dt.find("/uart@2000").unwrap();
+    let parent = uart.property_u32("interrupt-parent").unwrap();
+    assert_eq!(
+        dt.find_by_phandle(parent).unwrap().name(),
+        "interrupt-controller@1000"
+    );
+    let clock = uart.property_u32("clocks").unwrap();
+    assert_eq!(dt.find_by_phandle(clock).unwrap().name(), "clock");
+
+    for node in dt.iter().filter(|n| n.phandle_value().is_some()) {
+        let phandle = node.phandle_value().unwrap();
+        assert_eq!(dt.find_by_phandle(phandle), Some(node));
+    }
+    assert_eq!(dt.phandle_map().len(), 3);
+}
+
+#[test]
+fn phandle_index() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let index = dt.build_phandle_index();
+    let path = |phandle| index.get(phandle).map(NodePath::as_str);
+
+    assert_eq!(path(0x17), Some("/soc/uart@7e201000"));
+    assert_eq!(path(0x24), Some("/soc"));
+    assert_eq!(path(0x5), Some("/clocks/clock@0"));
+    assert_eq!(path(0xffff), None);
+    assert!(index.duplicates().is_empty());
+
+    assert_eq!(index.iter().count(), index.len());
+    for (phandle, path) in index.iter() {
+        assert_eq!(dt.find(path.as_str()), dt.find_by_phandle(phandle));
+    }
+}
+
+#[test]
+fn phandle_index_duplicates() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("a").property_u32("phandle", 1);
+    root.child("b").property_u32("phandle", 2);
+    root.child("c").property_u32("linux,phandle", 1);
+    let dt = builder.build().unwrap();
+    let index = dt.build_phandle_index();
+
+    // the same policy as the map: the first node wins, the rest are reported
+    assert_eq!(index.get(1).unwrap().as_str(), "/a");
+    assert_eq!(index.len(), 2);
+    assert_eq!(index.duplicates(), [1]);
+    assert_eq!(index.duplicates(), dt.phandle_map().duplicates());
+}
+
+#[test]
+fn phandle_map_lookup() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let map = dt.phandle_map();
+
+    assert!(map.duplicates().is_empty());
+    assert_eq!(map.get(0x17).unwrap().name(), "uart@7e201000");
+    assert_eq!(map.get(0x24).unwrap().name(), "soc");
+    assert!(map.get(0xffff).is_none());
+    assert_eq!(map.len(), dt.build_phandle_index().len());
+
+    // the root's interrupt-parent
+    let intc = map.get(dt.root.property_u32("interrupt-parent").unwrap());
+    assert_eq!(intc.unwrap().name(), "interrupt-controller@7e00b200");
+}
+
+#[test]
+fn phandle_map_interrupt_chain() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("interrupt-parent", 1);
+    root.child("gic@1000")
+        .property("interrupt-controller", &[])
+        .property_u32("phandle", 1);
+    root.child("gpio@2000")
+        .property("interrupt-controller", &[])
+        .property_u32("interrupt-parent", 1)
+        .property_u32("linux,phandle", 2);
+    root.child("button").property_u32("interrupt-parent", 2);
+    let dt = builder.build().unwrap();
+    let map = dt.phandle_map();
+
+    let mut chain = Vec::new();
+    let mut node = dt.find("/button").unwrap();
+    while let Some(phandle) = node.property_u32("interrupt-parent") {
+        node = map.get(phandle).unwrap();
+        chain.push(node.name());
+    }
+
+    assert_eq!(chain, ["gpio@2000", "gic@1000"]);
+}
+
+#[test]
+fn phandle_map_duplicates() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("a").property_u32("phandle", 1);
+    root.child("b").property_u32("phandle", 1);
+    root.child("c").property_u32("linux,phandle", 1);
+    root.child("d").property_u32("phandle", 2);
+    let dt = builder.build().unwrap();
+    let map = dt.phandle_map();
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(1).unwrap().name(), "a");
+    assert_eq!(map.duplicates(), &[1, 1]);
+}
+
+#[test]
+fn empty_phandle_map() {
+    let dt = DeviceTreeBuilder::new().build().unwrap();
+    let map = dt.phandle_map();
+
+    assert!(map.is_empty());
+    assert!(map.get(1).is_none());
+}
+
+fn reference(path: &str, property: &str, args: &[u32]) -> Reference {
+    Reference {
+        path: path.to_owned(),
+        property: property.to_owned(),
+        args: args.to_vec(),
+    }
+}
+
+#[test]
+fn reverse_references() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("interrupt-parent", 1);
+    root.child("intc")
+        .property_u32("phandle", 1)
+        .property_u32("#interrupt-cells", 1);
+    root.child("pll")
+        .property_u32("phandle", 2)
+        .property_u32("#clock-cells", 1);
+    root.child("osc")
+        .property_u32("phandle", 3)
+        .property_u32("#clock-cells", 0);
+    root.child("regulator").property_u32("phandle", 4);
+    root.child("uart@1000")
+        // <&pll 3>, <0>, <&osc>
+        .property("clocks", &[0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 3])
+        .property_u32("vdd-supply", 4)
+        // not a known property, but starts with a phandle
+        .property("custom-ref", &[0, 0, 0, 3, 0, 0, 0, 9])
+        .property_u32("#size-cells", 1)
+        .property("reg", &[0, 0, 0, 2, 0, 0, 0, 1]);
+    let dt = builder.build().unwrap();
+
+    let index = dt.phandle_references();
+
+    assert_eq!(
+        index.references_to(1),
+        &[reference("/", "interrupt-parent", &[])]
+    );
+    assert_eq!(
+        index.references_to(2),
+        &[reference("/uart@1000", "clocks", &[3])]
+    );
+    assert_eq!(
+        index.references_to(3),
+        &[
+            reference("/uart@1000", "clocks", &[]),
+            reference("/uart@1000", "custom-ref", &[9]),
+        ]
+    );
+    assert_eq!(
+        index.references_to(4),
+        &[reference("/uart@1000", "vdd-supply", &[])]
+    );
+    assert!(index.references_to(5).is_empty());
+}
+
+#[test]
+fn truncated_reference_list() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("gpio")
+        .property_u32("phandle", 1)
+        .property_u32("#gpio-cells", 2);
+    // the second entry is missing an argument
+    root.child("led").property(
+        "enable-gpios",
+        &[0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 6],
+    );
+    let dt = builder.build().unwrap();
+
+    assert_eq!(
+        dt.phandle_references().references_to(1),
+        &[reference("/led", "enable-gpios", &[5, 0])]
+    );
+}
+
+#[test]
+fn rpi_references() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let index = dt.phandle_references();
+
+    // clocks = <3 4>, two providers without arguments
+    for phandle in [3, 4] {
+        assert!(index.references_to(phandle).contains(&reference(
+            "/soc/uart@7e201000",
+            "clocks",
+            &[]
+        )));
+    }
+    assert!(index.references_to(16).contains(&reference(
+        "/soc/leds/act",
+        "gpios",
+        &[47, 0]
+    )));
+}
+
+/// A clock provider with one argument cell, one without and a consumer.
+fn clock_tree(clocks: &[u8]) -> DeviceTree {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("pll")
+        .property_u32("phandle", 1)
+        .property_u32("#clock-cells", 1);
+    root.child("osc")
+        .property_u32("phandle", 2)
+        .property_u32("#clock-cells", 0);
+    root.child("gpio").property_u32("phandle", 3);
+    root.child("uart").property("clocks", clocks);
+    builder.build().unwrap()
+}
+
+#[test]
+fn phandle_args() {
+    // <&pll 3>, <0>, <&osc>, <&pll 4>
+    let dt = clock_tree(&[
+        0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 4,
+    ]);
+    let uart = dt.find("/uart").unwrap();
+
+    let clocks = dt
+        .parse_phandle_args(uart, "clocks", "#clock-cells")
+        .unwrap();
+    let clocks: Vec<_> = clocks
+        .iter()
+        .map(|entry| (entry.target.name(), entry.args.clone()))
+        .collect();
+
+    assert_eq!(
+        clocks,
+        [("pll", vec![3]), ("osc", vec![]), ("pll", vec![4])]
+    );
+}
+
+#[test]
+fn phandle_args_errors() {
+    let cases: [(&[u8], PropError); 4] = [
+        // <&pll> without its argument
+        (&[0, 0, 0, 1], PropError::Truncated),
+        (&[0, 0, 0, 9], PropError::UnknownPhandle(9)),
+        // the gpio node has no #clock-cells
+        (&[0, 0, 0, 3], PropError::MissingCellCount),
+        (&[0, 0, 0, 2, 0], PropError::NotCellAligned),
+    ];
+
+    for (clocks, err) in cases {
+        let dt = clock_tree(clocks);
+        let uart = dt.find("/uart").unwrap();
+
+        assert_eq!(
+            dt.parse_phandle_args(uart, "clocks", "#clock-cells"),
+            Err(Error::PropError(err))
+        );
+    }
+
+    let dt = clock_tree(&[]);
+    let uart = dt.find("/uart").unwrap();
+    assert_eq!(
+        dt.parse_phandle_args(uart, "resets", "#reset-cells"),
+        Err(Error::PropError(PropError::NotFound))
+    );
+    assert_eq!(
+        dt.parse_phandle_args(uart, "clocks", "#clock-cells"),
+        Ok(Vec::new())
+    );
+}
+
+#[test]
+fn fixup_phandles() {
+    let original = DeviceTree::load(DTB).unwrap();
+    let mut dt = original.clone();
+    dt.fixup_phandles(0x100).unwrap();
+
+    for (before, after) in original.iter().zip(dt.iter()) {
+        assert_eq!(
+            after.phandle_value(),
+            before.phandle_value().map(|p| p + 0x100)
+        );
+    }
+
+    assert_eq!(dt.root.property_u32("interrupt-parent"), Some(0x101));
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    let clocks = dt.parse_phandle_args(uart, "clocks", "#clock-cells");
+    let clocks = clocks.unwrap();
+    assert_eq!(clocks.len(), 2);
+    assert_eq!(clocks[0].target.name(), "clock@2");
+    assert_eq!(clocks[1].target.name(), "clock@3");
+
+    // properties that are not references are left alone
+    assert_eq!(
+        uart.property("reg"),
+        original.find("/soc/uart@7e201000").unwrap().property("reg")
+    );
+}
+
+#[test]
+fn fixup_phandles_explicit() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("dma")
+        .property_u32("phandle", 1)
+        .property_u32("#dma-cells", 1);
+    root.child("clk")
+        .property_u32("phandle", 2)
+        .property_u32("#clock-cells", 1);
+    root.child("spi")
+        .property("dmas", &[0, 0, 0, 1, 0, 0, 0, 1])
+        .property("clocks", &[0, 0, 0, 2, 0, 0, 0, 5])
+        .property_u32("interrupt-parent", 1);
+    let mut dt = builder.build().unwrap();
+
+    dt.fixup_phandles_explicit(0x10, &["dmas", "clocks"])
+        .unwrap();
+
+    let spi = dt.find("/spi").unwrap();
+    let cells = |name| spi.property(name).unwrap().cells().collect::<Vec<_>>();
+    // every cell of a property of unknown format is taken as a phandle
+    assert_eq!(cells("dmas"), [0x11, 0x11]);
+    // but the format of known properties is still used
+    assert_eq!(cells("clocks"), [0x12, 5]);
+    // and properties that are not listed are left alone
+    assert_eq!(cells("interrupt-parent"), [1]);
+    assert_eq!(dt.find("/dma").unwrap().phandle_value(), Some(0x11));
+    assert_eq!(dt.find("/clk").unwrap().phandle_value(), Some(0x12));
+}
+
+#[test]
+fn fixup_phandles_overflow() {
+    let original = DeviceTree::load(DTB).unwrap();
+    let mut dt = original.clone();
+
+    assert_eq!(
+        dt.fixup_phandles(u32::MAX - 0x10),
+        Err(TreeError::PhandleOverflow)
+    );
+    assert_eq!(dt.fixup_phandles(u32::MAX), Err(TreeError::PhandleOverflow));
+    assert_eq!(dt, original);
+}
+
+#[test]
+fn fixup_phandles_overflow_of_legacy_phandle() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("a").property_u32("phandle", 2);
+    // only the legacy property is close to the limit
+    root.child("b")
+        .property_u32("phandle", 1)
+        .property_u32("linux,phandle", 0xffff_fff0);
+    let original = builder.build().unwrap();
+    let mut dt = original.clone();
+
+    assert_eq!(dt.fixup_phandles(0x10), Err(TreeError::PhandleOverflow));
+    assert_eq!(dt.fixup_phandles(0x0f), Err(TreeError::PhandleOverflow));
+    assert_eq!(dt, original);
+    assert_eq!(dt.fixup_phandles(0x0e), Ok(()));
+    let b = dt.find("/b").unwrap();
+    assert_eq!(b.property_u32("linux,phandle"), Some(0xffff_fffe));
+}
+
+#[test]
+fn fixup_phandles_overflow_leaves_tree_alone() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("a").property_u32("phandle", 1);
+    // a malformed phandle of two cells, the first close to the limit
+    root.child("b")
+        .property("phandle", &[0xff, 0xff, 0xff, 0xf0, 0, 0, 0, 0]);
+    let original = builder.build().unwrap();
+    let mut dt = original.clone();
+
+    assert_eq!(dt.fixup_phandles(0x20), Err(TreeError::PhandleOverflow));
+    assert_eq!(dt, original);
+    assert_eq!(dt.find("/a").unwrap().phandle_value(), Some(1));
+}
+
+#[test]
+fn max_and_all_phandles() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let phandles = dt.all_phandles();
+    assert_eq!(phandles.len(), dt.phandle_map().len());
+    assert!(phandles.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(dt.max_phandle(), *phandles.last().unwrap());
+    assert!(dt.find_by_phandle(dt.max_phandle() + 1).is_none());
+
+    let empty = DeviceTree::new(Node::new(""));
+    assert_eq!(empty.max_phandle(), 0);
+    assert!(empty.all_phandles().is_empty());
+}
+
+#[test]
+fn all_phandles_with_duplicates() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("a").property_u32("phandle", 3);
+    root.child("b")
+        .property_u32("phandle", 1)
+        .property_u32("linux,phandle", 1);
+    root.child("c").property_u32("linux,phandle", 3);
+    // only referred to, not defined
+    root.child("d").property_u32("interrupt-parent", 9);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.all_phandles(), [1, 3]);
+    assert_eq!(dt.phandle_map().duplicates(), [3]);
+    assert_eq!(dt.max_phandle(), 3);
+}
@@ -0,0 +1,254 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+fn prop(name: &str, data: &[u8]) -> Property {
+    Property::new(name, data.to_vec())
+}
+
+#[test]
+fn integer_values() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let clock = dt.find("/clocks/clock@0").unwrap();
+
+    let freq = clock.property("clock-frequency").unwrap();
+    assert_eq!(freq.as_u32(), Some(250_000_000));
+    assert_eq!(freq.as_u64(), Some(250_000_000));
+
+    let reg = dt.find("/memory").unwrap().property("reg").unwrap();
+    assert_eq!(reg.as_u64(), Some(0));
+    assert_eq!(reg.as_u32(), None);
+
+    assert_eq!(prop("x", &[1, 2, 3]).as_u32(), None);
+    assert_eq!(prop("x", &[]).as_u64(), None);
+}
+
+#[test]
+fn u64_from_cells() {
+    let one = prop("x", &[0x12, 0x34, 0x56, 0x78]);
+    assert_eq!(one.as_u64(), Some(0x1234_5678));
+    assert_eq!(one.cell_count(), 1);
+
+    let two = prop("x", &[0, 0, 0, 0x1, 0x12, 0x34, 0x56, 0x78]);
+    assert_eq!(two.as_u64(), Some(0x1_1234_5678));
+    assert_eq!(two.cell_count(), 2);
+
+    let three = prop("x", &[0; 12]);
+    assert_eq!(three.as_u64(), None);
+    assert_eq!(three.cell_count(), 3);
+
+    assert_eq!(prop("x", &[0; 6]).cell_count(), 1);
+}
+
+#[test]
+fn string_values() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let model = dt.root.property("model").unwrap();
+    assert_eq!(model.as_str(), Some("Raspberry Pi 2 Model B"));
+
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    let compatible = uart.property("compatible").unwrap();
+    let list: Vec<_> = compatible.as_strlist().collect();
+    assert_eq!(list, ["arm,pl011", "arm,primecell"]);
+
+    // only the first entry of a list is returned
+    assert_eq!(
+        uart.property("compatible").unwrap().as_str(),
+        Some("arm,pl011")
+    );
+    assert_eq!(prop("x", b"a\0b\xff\0").as_str(), Some("a"));
+    assert_eq!(prop("x", b"\0").as_str(), Some(""));
+
+    assert_eq!(prop("x", b"abc").as_str(), None);
+    assert_eq!(prop("x", &[0xff, 0]).as_str(), None);
+    assert_eq!(prop("x", &[]).as_str(), None);
+    assert_eq!(prop("x", &[]).as_strlist().count(), 0);
+}
+
+#[test]
+fn cell_array_values() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let ranges = dt.find("/soc").unwrap().property("ranges").unwrap();
+    assert_eq!(
+        ranges.as_u32_array(),
+        Some(vec![0x7e00_0000, 0x3f00_0000, 0x0100_0000])
+    );
+
+    assert_eq!(prop("x", &[]).as_u32_array(), Some(vec![]));
+    assert_eq!(prop("x", &[0, 0, 0, 1, 0]).as_u32_array(), None);
+}
+
+#[test]
+fn reg_values() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    let reg = uart.property("reg").unwrap();
+    assert_eq!(reg.as_reg(1, 1), Some(vec![(0x7e20_1000, 0x1000)]));
+    assert_eq!(reg.as_reg(2, 0), Some(vec![(0x7e20_1000_0000_1000, 0)]));
+    assert_eq!(reg.as_reg(2, 1), None);
+
+    let reg64 = prop(
+        "reg",
+        &[
+            0, 0, 0, 0x1, 0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0, 0, 0, // first
+            0, 0, 0, 0x2, 0, 0, 0, 0, 0, 0, 0, 0x1, 0, 0, 0, 0, // second
+        ],
+    );
+    assert_eq!(
+        reg64.as_reg(2, 2),
+        Some(vec![
+            (0x1_0000_0000, 0x8000_0000),
+            (0x2_0000_0000, 0x1_0000_0000)
+        ])
+    );
+    assert_eq!(reg64.as_reg(3, 1), None);
+}
+
+#[test]
+fn string_lists() {
+    let list = |data: &[u8]| -> Vec<String> {
+        prop("x", data).strings().map(String::from).collect()
+    };
+
+    assert!(list(b"").is_empty());
+    assert_eq!(list(b"\0"), [""]);
+    assert_eq!(list(b"one\0"), ["one"]);
+    assert_eq!(list(b"one\0two\0three\0"), ["one", "two", "three"]);
+
+    // malformed entries end the iteration
+    assert_eq!(list(b"one\0two"), ["one"]);
+    assert_eq!(list(b"one\0\xff\0three\0"), ["one"]);
+
+    let compatible = prop("compatible", b"snps,dwmac-4.10a\0snps,dwmac\0");
+    assert!(compatible.contains_string("snps,dwmac"));
+    assert!(!compatible.contains_string("snps"));
+}
+
+#[test]
+fn cell_values() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+
+    let interrupts = uart.property("interrupts").unwrap();
+    assert_eq!(interrupts.cells().collect::<Vec<_>>(), [2, 25]);
+    assert_eq!(interrupts.cell(0), Some(2));
+    assert_eq!(interrupts.cell(1), Some(25));
+    assert_eq!(interrupts.cell(2), None);
+    assert!(interrupts.is_cell_aligned());
+
+    let unaligned = prop("x", &[0, 0, 0, 1, 0, 0]);
+    assert_eq!(unaligned.cells().collect::<Vec<_>>(), [1]);
+    assert_eq!(unaligned.cell(1), None);
+    assert!(!unaligned.is_cell_aligned());
+
+    assert_eq!(prop("x", &[]).cells().count(), 0);
+}
+
+#[test]
+fn classify_values() {
+    use PropertyValue::*;
+
+    assert_eq!(prop("x", &[]).value(), Empty);
+    assert_eq!(prop("x", b"okay\0").value(), String("okay"));
+    assert_eq!(prop("x", b"a,b\0c\0").value(), StringList(vec!["a,b", "c"]));
+    assert_eq!(prop("x", &[0, 0, 0, 1]).value(), U32(1));
+    assert_eq!(
+        prop("x", &[0, 0, 0, 1, 0, 0, 0, 2]).value(),
+        Cells(vec![1, 2])
+    );
+    assert_eq!(prop("x", &[1, 2, 3]).value(), Bytes(&[1, 2, 3]));
+    // an empty string is not printable text
+    assert_eq!(prop("x", &[0]).value(), Bytes(&[0]));
+    assert_eq!(prop("x", b"a\0\0b\0").value(), Bytes(b"a\0\0b\0"));
+
+    // looks like "abc", but a phandle is always a cell
+    assert_eq!(prop("x", b"abc\0").value(), String("abc"));
+    assert_eq!(prop("phandle", b"abc\0").value(), Phandle(0x6162_6300));
+    // but only if the value is made up of cells
+    assert_eq!(prop("reg", b"abcdef\0").value(), String("abcdef"));
+}
+
+#[test]
+fn classify_phandles() {
+    use PropertyValue::*;
+
+    assert_eq!(prop("phandle", &[0, 0, 0, 7]).value(), Phandle(7));
+    assert_eq!(prop("linux,phandle", &[0, 0, 0, 7]).value(), Phandle(7));
+    assert_eq!(prop("interrupt-parent", &[0, 0, 0, 1]).value(), Phandle(1));
+    // a list of phandles is left to the caller
+    assert_eq!(
+        prop("interrupt-parent", &[0, 0, 0, 1, 0, 0, 0, 2]).value(),
+        Cells(vec![1, 2])
+    );
+
+    let dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(
+        uart.property("compatible").unwrap().value(),
+        StringList(vec!["arm,pl011", "arm,primecell"])
+    );
+    assert_eq!(uart.property("phandle").unwrap().value(), Phandle(23));
+}
+
+#[test]
+fn infer_value_is_value() {
+    for (_, prop) in DeviceTree::load(DTB).unwrap().properties_with_paths() {
+        let value: PropValue = prop.infer_value();
+        assert_eq!(value, prop.value());
+    }
+}
+
+#[test]
+fn decode_values() {
+    let cells = prop("linux,initrd-start", &[0, 0, 0, 1, 0, 0, 0, 2]);
+    assert_eq!(
+        cells.decode_as(PropHint::U64),
+        Some(PropertyValue::U64(0x1_0000_0002))
+    );
+    assert_eq!(
+        cells.decode_as(PropHint::Cells),
+        Some(PropertyValue::Cells(vec![1, 2]))
+    );
+    assert_eq!(cells.decode_as(PropHint::U32), None);
+    assert_eq!(cells.decode_as(PropHint::Phandle), None);
+    assert_eq!(cells.decode_as(PropHint::String), None);
+    assert_eq!(cells.decode_as(PropHint::Empty), None);
+    assert_eq!(
+        cells.decode_as(PropHint::Bytes),
+        Some(PropertyValue::Bytes(&[0, 0, 0, 1, 0, 0, 0, 2]))
+    );
+
+    let cell = prop("x", &[0, 0, 0, 9]);
+    assert_eq!(cell.decode_as(PropHint::U64), Some(PropertyValue::U64(9)));
+    assert_eq!(
+        cell.decode_as(PropHint::Phandle),
+        Some(PropertyValue::Phandle(9))
+    );
+
+    let strings = prop("x", b"a\0\0b\0");
+    assert_eq!(strings.decode_as(PropHint::String), None);
+    assert_eq!(
+        strings.decode_as(PropHint::StringList),
+        Some(PropertyValue::StringList(vec!["a", "", "b"]))
+    );
+    assert_eq!(
+        prop("x", b"okay\0").decode_as(PropHint::String),
+        Some(PropertyValue::String("okay"))
+    );
+    assert_eq!(prop("x", b"okay").decode_as(PropHint::StringList), None);
+    assert_eq!(prop("x", &[0xff, 0]).decode_as(PropHint::String), None);
+
+    assert_eq!(
+        prop("x", &[]).decode_as(PropHint::Empty),
+        Some(PropertyValue::Empty)
+    );
+    assert_eq!(
+        prop("x", &[]).decode_as(PropHint::Cells),
+        Some(PropertyValue::Cells(vec![]))
+    );
+}
@@ -1,9 +1,9 @@
-extern crate device_tree;
+extern crate flat_device_tree;
 
 use std::fs;
 use std::io::{Read, Write};
 
-use device_tree::*;
+use flat_device_tree::*;
 
 #[test]
 fn roundtrip() {
@@ -15,6 +15,7 @@ fn roundtrip() {
     let mut output = fs::OpenOptions::new()
         .write(true)
         .create(true)
+        .truncate(true)
         .open("output.dtb")
         .unwrap();
     output.write_all(&dtb).unwrap();
@@ -26,3 +27,36 @@ fn roundtrip() {
 
     assert!(original_fdt == generated_fdt);
 }
+
+#[test]
+fn store_layout() {
+    let buf = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+    let dt = DeviceTree::load(buf).unwrap();
+
+    let dtb = dt.store().unwrap();
+    let stored = DeviceTree::load(&dtb).unwrap();
+    let header = stored.header();
+
+    assert_eq!(header.totalsize as usize, dtb.len());
+    assert_eq!(header.off_mem_rsvmap % 8, 0);
+    assert!(header.off_mem_rsvmap < header.off_dt_struct);
+    assert_eq!(header.off_dt_struct % 4, 0);
+    assert_eq!(
+        header.off_dt_struct + header.size_dt_struct,
+        header.off_dt_strings
+    );
+    assert_eq!(
+        (header.off_dt_strings + header.size_dt_strings) as usize,
+        dtb.len()
+    );
+
+    // every property name is stored once
+    let start = header.off_dt_strings as usize;
+    let strings = &dtb[start..start + header.size_dt_strings as usize];
+    let mut names: Vec<_> = strings.split(|&b| b == 0).collect();
+    names.pop();
+    let count = names.len();
+    names.sort();
+    names.dedup();
+    assert_eq!(names.len(), count);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "serde")]
+
+extern crate flat_device_tree;
+extern crate serde_json;
+
+use flat_device_tree::*;
+use serde_json::json;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn serialize_tree() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let value = serde_json::to_value(&dt).unwrap();
+
+    assert_eq!(value["version"], 17);
+    assert_eq!(value["boot_cpuid_phys"], 0);
+    assert_eq!(value["reserved"], json!([]));
+    assert_eq!(value["root"]["name"], "");
+
+    let model = value["root"]["props"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|prop| prop["name"] == "model")
+        .unwrap();
+    // "Raspberry Pi 2 Model B\\0"
+    assert_eq!(
+        model["data"],
+        "5261737062657272792050692032204d6f64656c204200"
+    );
+
+    let soc = value["root"]["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|node| node["name"] == "soc")
+        .unwrap();
+    assert!(soc["children"].as_array().unwrap().len() > 10);
+}
+
+#[test]
+fn serialize_records() {
+    let reservation = MemoryReservation {
+        address: 0x1000,
+        size: 0x20,
+    };
+    assert_eq!(
+        serde_json::to_value(reservation).unwrap(),
+        json!({ "address": 0x1000, "size": 0x20 })
+    );
+
+    let header = *DeviceTree::load(DTB).unwrap().header();
+    let value = serde_json::to_value(header).unwrap();
+    assert_eq!(value["magic"], 0xd00dfeed_u32);
+    assert_eq!(value["off_dt_struct"], 0x38);
+    assert_eq!(
+        serde_json::from_value::<DeviceTreeHeader>(value).unwrap(),
+        header
+    );
+}
+
+#[test]
+fn json_roundtrip_to_dtb() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let json = serde_json::to_string(&dt).unwrap();
+
+    let parsed: DeviceTree = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed, dt);
+
+    let blob = parsed.store().unwrap();
+    assert_eq!(DeviceTree::load(&blob).unwrap(), dt);
+}
+
+#[test]
+fn reject_invalid_hex() {
+    for data in ["abc", "zz", "é0"] {
+        let prop = json!({ "name": "reg", "data": data });
+        assert!(
+            serde_json::from_value::<Property>(prop).is_err(),
+            "{}",
+            data
+        );
+    }
+
+    let prop = json!({ "name": "reg", "data": "0000Ff01" });
+    assert_eq!(
+        serde_json::from_value::<Property>(prop).unwrap().data(),
+        [0, 0, 0xff, 1]
+    );
+}
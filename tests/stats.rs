@@ -0,0 +1,46 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn rpi_stats() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let stats = dt.stats();
+
+    assert_eq!(stats.nodes, dt.iter_dfs().count());
+    assert_eq!(stats.properties, 439);
+    assert_eq!(stats.max_depth, 3);
+    assert_eq!(stats.property_bytes, 4132);
+    assert_eq!(stats.property_names, 117);
+    assert_eq!(stats.struct_size, 0x2a30);
+    assert_eq!(stats.strings_size, 0x4d4);
+
+    let text = stats.to_string();
+    assert!(text.starts_with(&format!("nodes: {}\n", stats.nodes)));
+    assert!(text.ends_with("strings block: 1236 bytes"));
+}
+
+#[test]
+fn built_tree_stats() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_str("model", "test");
+    root.child("a").child("b").property_u32("reg", 1);
+    root.child("c").property_u32("reg", 2);
+    let dt = builder.build().unwrap();
+
+    assert_eq!(
+        dt.stats(),
+        TreeStats {
+            nodes: 4,
+            properties: 3,
+            max_depth: 2,
+            property_bytes: 13,
+            property_names: 2,
+            struct_size: 0,
+            strings_size: 0,
+        }
+    );
+}
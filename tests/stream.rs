@@ -0,0 +1,112 @@
+extern crate flat_device_tree;
+
+mod common;
+
+use common::*;
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn events_match_tree() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let events: Vec<_> = StreamingParser::new(DTB)
+        .unwrap()
+        .collect::<Result<_>>()
+        .unwrap();
+
+    let nodes = dt.iter_dfs().count();
+    let props: usize = dt.iter_dfs().map(|n| n.properties().len()).sum();
+    let count =
+        |f: fn(&ParseEvent) -> bool| events.iter().filter(|e| f(e)).count();
+
+    assert_eq!(count(|e| matches!(e, ParseEvent::BeginNode { .. })), nodes);
+    assert_eq!(count(|e| matches!(e, ParseEvent::EndNode)), nodes);
+    assert_eq!(count(|e| matches!(e, ParseEvent::Property { .. })), props);
+    assert_eq!(events[0], ParseEvent::BeginNode { name: "" });
+    assert_eq!(events[events.len() - 2], ParseEvent::EndNode);
+    assert_eq!(events.last(), Some(&ParseEvent::End));
+}
+
+#[test]
+fn find_property_without_tree() {
+    let mut parser = StreamingParser::new(DTB).unwrap();
+
+    let model = parser.find_map(|event| match event {
+        Ok(ParseEvent::Property {
+            name: "model",
+            data,
+        }) => Some(data),
+        _ => None,
+    });
+    assert_eq!(model, Some(&b"Raspberry Pi 2 Model B\0"[..]));
+    // still inside the root node
+    assert_eq!(parser.depth(), 1);
+}
+
+#[test]
+fn skips_nop_tokens() {
+    let structure = Structure::default()
+        .token(NOP)
+        .begin_node("")
+        .token(NOP)
+        .prop(0, &[0, 0, 0, 1])
+        .token(NOP)
+        .token(END_NODE)
+        .token(NOP)
+        .token(END);
+    let dtb = blob(&structure.0, b"cell\0");
+
+    let events: Vec<_> = StreamingParser::new(&dtb).unwrap().collect();
+    assert_eq!(
+        events,
+        [
+            Ok(ParseEvent::BeginNode { name: "" }),
+            Ok(ParseEvent::Property {
+                name: "cell",
+                data: &[0, 0, 0, 1],
+            }),
+            Ok(ParseEvent::EndNode),
+            Ok(ParseEvent::End),
+        ]
+    );
+}
+
+#[test]
+fn stops_at_first_error() {
+    // a property outside of any node
+    let structure = Structure::default().prop(0, &[]).token(END);
+    let dtb = blob(&structure.0, b"cell\0");
+    let events: Vec<_> = StreamingParser::new(&dtb).unwrap().collect();
+    assert_eq!(events, [Err(Error::ParseError(56))]);
+
+    // a second root node
+    let structure = Structure::default()
+        .begin_node("")
+        .token(END_NODE)
+        .begin_node("")
+        .token(END_NODE)
+        .token(END);
+    let dtb = blob(&structure.0, b"");
+    let events: Vec<_> = StreamingParser::new(&dtb).unwrap().collect();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[2], Err(Error::ParseError(56 + 12)));
+
+    // the root node is never closed
+    let structure = Structure::default().begin_node("");
+    let dtb = blob(&structure.0, b"");
+    let end = 56 + structure.0.len();
+    let events: Vec<_> = StreamingParser::new(&dtb).unwrap().collect();
+    assert_eq!(
+        events[1],
+        Err(Error::SliceReadError(SliceReadError::UnexpectedEndOfInput(
+            end
+        )))
+    );
+    assert_eq!(events.len(), 2);
+
+    assert_eq!(
+        StreamingParser::new(&DTB[..DTB.len() - 4]).unwrap_err(),
+        Error::SizeMismatch
+    );
+}
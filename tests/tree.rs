@@ -0,0 +1,563 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn header_accessors() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.version(), 17);
+    assert_eq!(dt.boot_cpuid_phys(), 0);
+    assert_eq!(dt.totalsize() as usize, DTB.len());
+    assert_eq!(dt.off_dt_struct(), 0x38);
+    assert_eq!(dt.off_dt_strings(), 0x2a68);
+}
+
+#[test]
+fn root_accessor() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.root().name(), "");
+    assert!(dt.root().into_iter().any(|n| n.name() == "soc"));
+}
+
+#[test]
+fn new_tree_roundtrips() {
+    let mut root = Node::new("");
+    root.set_property("model", b"test\0".to_vec()).unwrap();
+    let dt = DeviceTree::new(root);
+
+    let loaded = DeviceTree::load(&dt.store().unwrap()).unwrap();
+    assert_eq!(loaded, dt);
+    assert_eq!(loaded.totalsize(), dt.store().unwrap().len() as u32);
+}
+
+#[test]
+fn memory_reservations_roundtrip() {
+    let mut dt = DeviceTree::new(Node::new(""));
+    dt.reserved.push(MemoryReservation {
+        address: 0x1000_0000,
+        size: 0x1000,
+    });
+    dt.reserved.push(MemoryReservation {
+        address: 0x8000_0000_0000,
+        size: 0x20_0000,
+    });
+
+    let loaded = DeviceTree::load(&dt.store().unwrap()).unwrap();
+
+    assert_eq!(loaded.memory_reservations(), dt.memory_reservations());
+    assert_eq!(loaded.memory_reservations().len(), 2);
+}
+
+#[test]
+fn empty_memory_reservation_block() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert!(dt.memory_reservations().is_empty());
+}
+
+#[test]
+fn find_node_by_path() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.find_node("/"), Some(&dt.root));
+    assert_eq!(dt.find_node("/soc").unwrap().name(), "soc");
+    assert_eq!(
+        dt.find_node("/soc/uart@7e201000").unwrap().name(),
+        "uart@7e201000"
+    );
+    assert_eq!(dt.find_node("/clocks/clock@1/").unwrap().name(), "clock@1");
+
+    assert!(dt.find_node("/soc/uart@7e000000").is_none());
+    assert!(dt.find_node("/nonexistent/node").is_none());
+    // relative paths must start with an alias
+    assert!(dt.find_node("cpus").is_none());
+}
+
+#[test]
+fn find_node_without_unit_address() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.find_node("/soc/gpio").unwrap().name(), "gpio@7e200000");
+    // there is more than one uart, so the short name is ambiguous
+    assert!(dt.find_node("/soc/uart").is_none());
+}
+
+#[test]
+fn header_and_root_mut() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+
+    let header = *dt.header();
+    assert_eq!(header.magic, 0xd00dfeed);
+    assert_eq!(header.off_mem_rsvmap, 0x28);
+    assert_eq!(header.size_dt_strings, 0x4d4);
+    assert_eq!(header.size_dt_struct, 0x2a30);
+
+    dt.root_mut().set_name("renamed").unwrap();
+    assert_eq!(dt.root().name(), "renamed");
+}
+
+#[test]
+fn find_node_mut_by_path() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+
+    let cpu = dt.find_node_mut("/cpus/cpu@2").unwrap();
+    cpu.remove_property("reg");
+    assert!(!dt.find_node("/cpus/cpu@2").unwrap().has_property("reg"));
+    assert!(dt.find_node("/cpus/cpu@1").unwrap().has_property("reg"));
+
+    assert_eq!(dt.find_node_mut("/").unwrap().name(), "");
+    assert!(dt.find_node_mut("/cpus/cpu@4").is_none());
+    assert!(dt.find_node_mut("cpus").is_none());
+}
+
+#[test]
+fn find_compatible_nodes() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    // "arm,primecell" is the second entry of the compatible list
+    let uart = dt.find_compatible("arm,primecell").next().unwrap();
+    assert_eq!(uart.name(), "uart@7e201000");
+    assert_eq!(dt.find_compatible("arm,pl011").next(), Some(uart));
+    assert_eq!(
+        dt.find_compatible("brcm,bcm2709").next().unwrap().name(),
+        ""
+    );
+
+    // entries are matched exactly, not as substrings
+    assert!(dt.find_compatible("arm,pl01").next().is_none());
+    assert!(dt.find_compatible("arm").next().is_none());
+
+    let clocks = dt.find_all_compatible("fixed-clock");
+    // clock@5 is a fixed-factor-clock
+    let names: Vec<_> = clocks.iter().map(|n| n.name()).collect();
+    assert_eq!(
+        names,
+        ["clock@0", "clock@1", "clock@2", "clock@3", "clock@4", "clock@6"]
+    );
+    assert!(dt.find_all_compatible("vendor,none").is_empty());
+}
+
+#[test]
+fn clone_is_independent() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let mut copy = dt.clone();
+    assert_eq!(copy, dt);
+
+    copy.root
+        .find_mut("/soc/uart@7e201000")
+        .unwrap()
+        .set_property("status", b"disabled\0".to_vec())
+        .unwrap();
+    copy.root.remove_child("memory");
+
+    assert_ne!(copy, dt);
+    assert!(dt.find("/soc/uart@7e201000").unwrap().is_enabled());
+    assert!(dt.find("/memory").is_some());
+    assert_eq!(dt, DeviceTree::load(DTB).unwrap());
+}
+
+#[test]
+fn independent_parses_are_equal() {
+    let first = DeviceTree::load(DTB).unwrap();
+    // parse a separate copy of the blob
+    let buf = DTB.to_vec();
+    let second = DeviceTree::load(&buf).unwrap();
+
+    assert_eq!(first, second);
+    assert_eq!(first.header(), second.header());
+    assert_eq!(first.root.children()[0], second.root.children()[0]);
+    assert_eq!(first.root.properties()[0], second.root.properties()[0]);
+}
+
+#[test]
+fn try_from_blob() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(DeviceTree::try_from(DTB), Ok(dt.clone()));
+    assert_eq!(DeviceTree::try_from(DTB.to_vec()), Ok(dt.clone()));
+    assert_eq!(DeviceTree::from_bytes(DTB), Ok(dt));
+
+    assert_eq!(
+        DeviceTree::try_from(&DTB[..DTB.len() - 1]),
+        Err(Error::SizeMismatch)
+    );
+    let dt: Result<DeviceTree> = vec![0; 40].try_into();
+    assert_eq!(dt, Err(Error::InvalidMagicNumber));
+}
+
+#[test]
+fn find_enabled_compatible_nodes() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("uart@1000")
+        .property_str("compatible", "ns16550a")
+        .property_str("status", "disabled");
+    root.child("uart@2000")
+        .property_str("compatible", "ns16550a");
+    root.child("uart@3000")
+        .property_str("compatible", "ns16550a")
+        .property_str("status", "fail-sss");
+    root.child("uart@4000")
+        .property_str("compatible", "ns16550a")
+        .property_str("status", "okay");
+    let dt = builder.build().unwrap();
+
+    assert_eq!(
+        dt.find_compatible("ns16550a").next().unwrap().name(),
+        "uart@1000"
+    );
+    assert_eq!(
+        dt.find_enabled_compatible("ns16550a")
+            .next()
+            .unwrap()
+            .name(),
+        "uart@2000"
+    );
+    let names: Vec<_> = dt
+        .find_all_enabled_compatible("ns16550a")
+        .iter()
+        .map(|n| n.name())
+        .collect();
+    assert_eq!(names, ["uart@2000", "uart@4000"]);
+
+    // every i2c controller of the fixture is disabled
+    let dt = DeviceTree::load(DTB).unwrap();
+    assert_eq!(dt.find_all_compatible("brcm,bcm2708-i2c").len(), 3);
+    assert!(dt
+        .find_enabled_compatible("brcm,bcm2708-i2c")
+        .next()
+        .is_none());
+    assert!(dt
+        .find_all_enabled_compatible("brcm,bcm2708-i2c")
+        .is_empty());
+}
+
+#[test]
+fn find_compatible_in_document_order() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let names: Vec<_> = dt
+        .find_compatible("fixed-clock")
+        .map(|n| n.name())
+        .collect();
+    assert_eq!(
+        names,
+        ["clock@0", "clock@1", "clock@2", "clock@3", "clock@4", "clock@6"]
+    );
+
+    // a driver for both uart types
+    let names: Vec<_> = dt
+        .find_compatible_any(&["brcm,bcm2835-aux-uart", "arm,pl011"])
+        .map(|n| n.name())
+        .collect();
+    assert_eq!(names, ["uart@7e201000", "uart@7e215040"]);
+
+    let enabled: Vec<_> = dt
+        .find_compatible_any(&["brcm,bcm2835-aux-uart", "arm,pl011"])
+        .filter(|n| n.is_enabled())
+        .collect();
+    assert_eq!(enabled.len(), 1);
+
+    assert_eq!(dt.find_compatible_any(&[]).count(), 0);
+}
+
+#[test]
+fn find_by_property() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let names: Vec<_> = dt
+        .find_with_property("interrupt-controller")
+        .map(|n| n.name())
+        .collect();
+    assert_eq!(names, ["interrupt-controller@7e00b200", "gpio@7e200000"]);
+    assert_eq!(dt.find_with_property("vendor,none").count(), 0);
+
+    let memory: Vec<_> = dt
+        .find_with_property_value("device_type", b"memory\0")
+        .collect();
+    assert_eq!(memory.len(), 1);
+    assert_eq!(memory[0].name(), "memory");
+    // the terminator is part of the value
+    assert_eq!(
+        dt.find_with_property_value("device_type", b"memory")
+            .count(),
+        0
+    );
+    assert_eq!(
+        dt.find_with_property_value("device_type", b"cpu\0").count(),
+        4
+    );
+
+    let okay = dt
+        .find_with_property_value("status", b"okay\0")
+        .filter(|n| n.is_enabled())
+        .count();
+    let enabled = dt
+        .find_with_property("status")
+        .filter(|n| n.is_enabled())
+        .count();
+    assert_eq!(okay, enabled);
+    assert_eq!(okay, 5);
+}
+
+#[test]
+fn board_identification() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.model(), Some("Raspberry Pi 2 Model B"));
+    assert_eq!(dt.linux_machine_compatible(), Some("brcm,bcm2709"));
+    assert_eq!(dt.serial_number(), None);
+
+    let mut builder = DeviceTreeBuilder::new();
+    builder
+        .root("")
+        .property_str("serial-number", "00000000c0ffee00")
+        .property("compatible", b"vendor,board-v2\0vendor,soc\0")
+        .property("model", b"no terminator");
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.serial_number(), Some("00000000c0ffee00"));
+    assert_eq!(dt.linux_machine_compatible(), Some("vendor,board-v2"));
+    assert_eq!(dt.model(), None);
+
+    let empty = DeviceTree::new(Node::new(""));
+    assert_eq!(empty.model(), None);
+    assert_eq!(empty.linux_machine_compatible(), None);
+}
+
+#[test]
+fn find_nodes_by_property() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let cpus = dt.find_nodes_by_property("device_type", b"cpu\0");
+    assert_eq!(cpus.len(), 4);
+    assert!(cpus.iter().all(|n| n.base_name() == "cpu"));
+    assert!(dt.find_nodes_by_property("device_type", b"cpu").is_empty());
+
+    // the root is searched too
+    let roots = dt.find_nodes_with_property("interrupt-parent");
+    assert_eq!(roots.len(), 1);
+    assert!(std::ptr::eq(roots[0], dt.root()));
+    let model = dt.root().property("model").unwrap();
+    let roots = dt.find_nodes_by_property("model", model.data());
+    assert!(std::ptr::eq(roots[0], dt.root()));
+
+    let controllers = dt.find_nodes_with_property("interrupt-controller");
+    assert!(controllers
+        .iter()
+        .copied()
+        .eq(dt.find_with_property("interrupt-controller")));
+    assert!(dt.find_nodes_with_property("vendor,none").is_empty());
+}
+
+#[test]
+fn extract_subtree() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let cpus = dt.subtree("/cpus").unwrap();
+    assert_eq!(cpus.root.name(), "");
+    assert_eq!(cpus.root.children().len(), 4);
+    assert_eq!(cpus.version, 17);
+    assert!(cpus.reserved.is_empty());
+    assert_eq!(cpus.find("/cpu@1"), dt.find("/cpus/cpu@1"));
+
+    let dtb = cpus.store().unwrap();
+    assert_eq!(DeviceTree::load(&dtb).unwrap(), cpus);
+
+    // the whole tree, without the memory reservations
+    let whole = dt.subtree("/").unwrap();
+    assert_eq!(whole.root, dt.root);
+
+    assert!(dt.subtree("/cpus/cpu@4").is_none());
+    assert!(dt.subtree("cpus").is_none());
+}
+
+#[test]
+fn prune_nodes() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+    let nodes = dt.iter().count();
+
+    assert!(dt.prune("/cpus/cpu@3"));
+    assert!(dt.find("/cpus/cpu@3").is_none());
+    assert_eq!(dt.iter().count(), nodes - 1);
+
+    // everything below the node goes with it
+    assert!(dt.prune("/cpus/"));
+    assert!(dt.find("/cpus").is_none());
+    assert_eq!(dt.iter().count(), nodes - 5);
+
+    assert!(!dt.prune("/cpus"));
+    assert!(!dt.prune("/"));
+    assert!(!dt.prune("memory"));
+    assert!(dt.find("/memory").is_some());
+}
+
+#[test]
+fn graft_nodes() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+    let cpus = dt.find("/cpus").unwrap().clone();
+    assert!(dt.prune("/cpus"));
+
+    let original = DeviceTree::load(DTB).unwrap();
+    dt.graft("/", cpus.clone()).unwrap();
+    assert_eq!(dt.find("/cpus"), original.find("/cpus"));
+    assert_eq!(dt.root.children().last().unwrap().name(), "cpus");
+
+    let before = dt.clone();
+    assert_eq!(
+        dt.graft("/", cpus.clone()),
+        Err(GraftError::DuplicateChildName)
+    );
+    assert_eq!(
+        dt.graft("/nonexistent", cpus.clone()),
+        Err(GraftError::ParentNotFound)
+    );
+
+    // Node::new() doesn't check the name, unlike set_name()
+    let invalid = Node::new("cpus#");
+    assert_eq!(dt.graft("/", invalid), Err(GraftError::InvalidNodeName));
+
+    let mut invalid = cpus;
+    invalid.set_name("more-cpus").unwrap();
+    invalid.children_mut()[0].properties_mut()[0] =
+        Property::new("bad name", Vec::new());
+    assert_eq!(dt.graft("/", invalid), Err(GraftError::InvalidPropertyName));
+
+    assert_eq!(dt, before);
+}
+
+#[test]
+fn rename_nodes() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap().clone();
+
+    dt.rename_node("/soc/uart@7e201000", "serial").unwrap();
+    assert!(dt.find("/soc/uart@7e201000").is_none());
+    let serial = dt.find("/soc/serial@7e201000").unwrap();
+    assert_eq!(serial.properties(), uart.properties());
+
+    dt.rename_node("/soc/leds", "indicators").unwrap();
+    assert!(dt.find("/soc/indicators").is_some());
+
+    let before = dt.clone();
+    assert_eq!(
+        dt.rename_node("/soc/serial@7e201000", "uart@0"),
+        Err(RenameError::InvalidNodeName)
+    );
+    assert_eq!(
+        dt.rename_node("/soc/indicators", ""),
+        Err(RenameError::InvalidNodeName)
+    );
+    assert_eq!(
+        dt.rename_node("/soc/indicators", "gpio leds"),
+        Err(RenameError::InvalidNodeName)
+    );
+    assert_eq!(
+        dt.rename_node("/soc/indicators", "fb"),
+        Err(RenameError::DuplicateChildName)
+    );
+    assert_eq!(
+        dt.rename_node("/soc/nonexistent", "uart"),
+        Err(RenameError::NodeNotFound)
+    );
+    assert_eq!(dt.rename_node("/", "root"), Err(RenameError::RootNode));
+
+    // a node is not its own sibling
+    dt.rename_node("/cpus/cpu@1", "cpu").unwrap();
+    assert_eq!(dt, before);
+}
+
+#[test]
+fn modify_by_path() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+
+    dt.set_property("/soc/uart@7e201000", "status", b"disabled\0".to_vec())
+        .unwrap();
+    dt.set_property("/soc/uart@7e201000", "current-speed", vec![0, 1, 194, 0])
+        .unwrap();
+    let uart = dt.find("/soc/uart@7e201000").unwrap();
+    assert_eq!(uart.property_str("status"), Some("disabled"));
+    assert_eq!(uart.property_u32("current-speed"), Some(115_200));
+
+    assert_eq!(dt.delete_property("/soc/uart@7e201000", "status"), Ok(true));
+    assert_eq!(
+        dt.delete_property("/soc/uart@7e201000", "status"),
+        Ok(false)
+    );
+    assert!(!dt
+        .find("/soc/uart@7e201000")
+        .unwrap()
+        .has_property("status"));
+
+    dt.add_node("/soc", "spi@7e2fe000").unwrap();
+    dt.set_property("/soc/spi@7e2fe000", "#address-cells", vec![0, 0, 0, 1])
+        .unwrap();
+    let spi = dt.find("/soc/spi@7e2fe000").unwrap();
+    assert_eq!(spi.property_u32("#address-cells"), Some(1));
+    assert!(spi.children().is_empty());
+
+    let before = dt.clone();
+    assert_eq!(
+        dt.set_property("/soc/nonexistent", "status", Vec::new()),
+        Err(TreeError::NodeNotFound)
+    );
+    assert_eq!(
+        dt.set_property("/soc", "bad name", Vec::new()),
+        Err(TreeError::InvalidPropertyName)
+    );
+    assert_eq!(
+        dt.delete_property("soc", "status"),
+        Err(TreeError::NodeNotFound)
+    );
+    assert_eq!(
+        dt.add_node("/soc", "spi@7e2fe000"),
+        Err(TreeError::DuplicateChildName)
+    );
+    assert_eq!(dt.add_node("/soc", "spi@"), Err(TreeError::InvalidNodeName));
+    assert_eq!(
+        dt.add_node("/nonexistent", "spi"),
+        Err(TreeError::NodeNotFound)
+    );
+    assert_eq!(dt, before);
+}
+
+#[test]
+fn flatten_tree() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_str("model", "board");
+    let mut soc = root.child("soc");
+    soc.property("ranges", &[]);
+    soc.child("uart@1000").property_u32("reg", 0x1000);
+    // a property and a child of the same name
+    root.child("chosen").property_str("aliases", "x");
+    root.child("aliases");
+    let dt = builder.build().unwrap();
+
+    let expected: Vec<(String, Vec<u8>)> = vec![
+        ("/:model".into(), b"board\0".to_vec()),
+        ("/soc:ranges".into(), vec![]),
+        ("/soc/uart@1000:reg".into(), vec![0, 0, 0x10, 0]),
+        ("/chosen:aliases".into(), b"x\0".to_vec()),
+    ];
+    assert_eq!(dt.flatten(), expected);
+
+    let dt = DeviceTree::load(DTB).unwrap();
+    let flat = dt.flatten();
+    assert_eq!(flat.len(), 439);
+    assert_eq!(flat[0].0, "/:#address-cells");
+    let uart = flat
+        .iter()
+        .find(|(key, _)| key == "/soc/uart@7e201000:status")
+        .unwrap();
+    assert_eq!(uart.1, b"okay\0");
+    // every key is unique
+    let mut keys: Vec<_> = flat.iter().map(|(key, _)| key).collect();
+    keys.sort();
+    keys.dedup();
+    assert_eq!(keys.len(), flat.len());
+}
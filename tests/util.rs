@@ -0,0 +1,91 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::util::{SliceRead, VecWrite};
+use flat_device_tree::SliceReadError;
+
+#[test]
+fn subslice_to_end_of_buffer() {
+    let buf: &[u8] = &[1, 2, 3, 4];
+
+    assert_eq!(buf.subslice(0, 4), Ok(buf));
+    assert_eq!(buf.subslice(2, 4), Ok(&buf[2..]));
+    assert_eq!(buf.subslice(4, 4), Ok(&buf[4..]));
+}
+
+#[test]
+fn subslice_past_end_of_buffer() {
+    let buf: &[u8] = &[1, 2, 3, 4];
+
+    assert_eq!(
+        buf.subslice(2, 5),
+        Err(SliceReadError::UnexpectedEndOfInput(2))
+    );
+    assert_eq!(
+        buf.subslice(3, 2),
+        Err(SliceReadError::UnexpectedEndOfInput(3))
+    );
+}
+
+#[test]
+fn read_last_bytes() {
+    let buf: &[u8] = &[0, 0, 0, 0, 0xd0, 0x0d, 0xfe, 0xed];
+
+    assert_eq!(buf.read_be_u32(4), Ok(0xd00dfeed));
+    assert_eq!(buf.read_be_u64(0), Ok(0xd00dfeed));
+    assert_eq!(
+        buf.read_be_u32(5),
+        Err(SliceReadError::UnexpectedEndOfInput(5))
+    );
+}
+
+#[test]
+fn read_endianness() {
+    let buf: &[u8] = &[0x12, 0x34, 0x56, 0x78];
+
+    assert_eq!(buf.read_be_u32(0), Ok(0x12345678));
+    assert_eq!(buf.read_le_u32(0), Ok(0x78563412));
+}
+
+#[test]
+fn read_widths() {
+    let buf: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8];
+
+    assert_eq!(buf.read_be_u16(0), Ok(0x0102));
+    assert_eq!(buf.read_be_u16(6), Ok(0x0708));
+    assert_eq!(buf.read_be_u64(0), Ok(0x0102030405060708));
+
+    assert_eq!(
+        buf.read_be_u16(7),
+        Err(SliceReadError::UnexpectedEndOfInput(7))
+    );
+    assert_eq!(
+        buf.read_be_u64(1),
+        Err(SliceReadError::UnexpectedEndOfInput(1))
+    );
+}
+
+#[test]
+fn write_to_short_buffer() {
+    let mut buf = Vec::new();
+
+    buf.write_be_u64(0, 0x0102030405060708).unwrap();
+    buf.write_be_u32(8, 0x090a0b0c).unwrap();
+
+    assert_eq!(buf, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+}
+
+#[test]
+fn read_unterminated_string() {
+    let buf: &[u8] = b"cell\0name";
+
+    assert_eq!(buf.read_bstring0(0), Ok(&b"cell"[..]));
+    assert_eq!(buf.read_bstring0(4), Ok(&b""[..]));
+    assert_eq!(
+        buf.read_bstring0(5),
+        Err(SliceReadError::UnexpectedEndOfInput(5))
+    );
+    assert_eq!(
+        buf.read_bstring0(20),
+        Err(SliceReadError::UnexpectedEndOfInput(20))
+    );
+}
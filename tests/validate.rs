@@ -0,0 +1,76 @@
+extern crate flat_device_tree;
+
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[test]
+fn rpi_is_valid() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    assert_eq!(dt.validate(), []);
+}
+
+#[test]
+fn reports_problems() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", 1)
+        .property_u32("#size-cells", 1);
+    root.child("intc@1000")
+        .property_u32("phandle", 1)
+        .property_u32("#interrupt-cells", 1);
+    root.child("intc@2000").property_u32("phandle", 1);
+    root.child("intc@3000").property_u32("phandle", 1);
+    root.child("uart@4000")
+        .property("reg", &[0, 0, 0x40, 0, 0, 0, 0x10])
+        .property_u32("interrupt-parent", 7);
+    root.child("led_1")
+        .property("gpios", &[0, 0, 0, 7, 0, 0, 0, 3]);
+    // the builder refuses invalid names, so patch one into the blob
+    let mut blob = builder.build().unwrap().store().unwrap();
+    let at = blob.windows(6).position(|w| w == b"led_1\0").unwrap();
+    blob[at + 3] = b'#';
+    let dt = DeviceTree::load(&blob).unwrap();
+
+    assert_eq!(
+        dt.validate(),
+        [
+            ValidationWarning::DuplicatePhandle(1),
+            ValidationWarning::RegSizeMismatch {
+                path: "/uart@4000".to_owned(),
+            },
+            ValidationWarning::MissingPhandle(7),
+            ValidationWarning::InvalidNodeName("/led#1".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn huge_cell_counts() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.property_u32("#address-cells", u32::MAX)
+        .property_u32("#size-cells", 1);
+    root.child("uart@4000").property_u32("reg", 0x4000);
+    let blob = builder.build().unwrap().store().unwrap();
+    let dt = DeviceTree::load(&blob).unwrap();
+
+    assert_eq!(
+        dt.validate(),
+        [ValidationWarning::RegSizeMismatch {
+            path: "/uart@4000".to_owned(),
+        }]
+    );
+}
+
+#[test]
+fn alias_paths_are_not_references() {
+    let mut builder = DeviceTreeBuilder::new();
+    let mut root = builder.root("");
+    root.child("aliases").property_str("clocks", "/clocks");
+    root.child("clocks");
+    let dt = builder.build().unwrap();
+
+    assert_eq!(dt.validate(), []);
+}
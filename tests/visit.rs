@@ -0,0 +1,229 @@
+extern crate flat_device_tree;
+
+use std::ops::ControlFlow;
+
+use flat_device_tree::visit::{VisitAction, Visitor, WalkAction};
+use flat_device_tree::*;
+
+const DTB: &[u8] = include_bytes!("../examples/bcm2709-rpi-2-b.dtb");
+
+#[derive(Default)]
+struct Counter {
+    nodes: usize,
+    props: usize,
+    max_depth: usize,
+}
+
+impl Visitor for Counter {
+    fn visit_node(&mut self, _node: &Node, depth: usize) -> VisitAction {
+        self.nodes += 1;
+        self.max_depth = self.max_depth.max(depth);
+        VisitAction::Continue
+    }
+
+    fn visit_property(&mut self, _prop: &Property) {
+        self.props += 1;
+    }
+}
+
+/// Finds the first compatible string starting with a prefix.
+struct FirstCompatible<'p> {
+    prefix: &'p str,
+    found: Option<String>,
+    visited: usize,
+}
+
+impl Visitor for FirstCompatible<'_> {
+    fn visit_node(&mut self, node: &Node, _depth: usize) -> VisitAction {
+        self.visited += 1;
+        let found = node
+            .property("compatible")
+            .and_then(|p| p.strings().find(|s| s.starts_with(self.prefix)));
+
+        match found {
+            Some(compat) => {
+                self.found = Some(compat.to_owned());
+                VisitAction::Stop
+            }
+            None => VisitAction::Continue,
+        }
+    }
+}
+
+/// Visits the top level of the tree only.
+struct TopLevel(Vec<String>);
+
+impl Visitor for TopLevel {
+    fn visit_node(&mut self, node: &Node, depth: usize) -> VisitAction {
+        self.0.push(node.name().to_owned());
+        if depth == 0 {
+            VisitAction::Continue
+        } else {
+            VisitAction::SkipChildren
+        }
+    }
+}
+
+#[test]
+fn count_nodes() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let mut counter = Counter::default();
+    dt.visit(&mut counter);
+
+    assert_eq!(counter.nodes, dt.iter_dfs().count());
+    assert_eq!(
+        counter.props,
+        dt.iter_dfs().map(|n| n.properties().len()).sum::<usize>()
+    );
+    assert_eq!(counter.max_depth, 3);
+}
+
+#[test]
+fn stop_at_first_match() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let mut visitor = FirstCompatible {
+        prefix: "arm,",
+        found: None,
+        visited: 0,
+    };
+    dt.visit(&mut visitor);
+
+    assert_eq!(visitor.found.as_deref(), Some("arm,pl011"));
+    assert!(visitor.visited < dt.iter_dfs().count());
+}
+
+#[test]
+fn skip_children() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let mut visitor = TopLevel(Vec::new());
+    dt.visit(&mut visitor);
+
+    assert_eq!(visitor.0.len(), dt.root.children().len() + 1);
+}
+
+#[test]
+fn walk_in_document_order() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let mut nodes = Vec::new();
+    let flow = dt.root.walk(|node, depth| {
+        nodes.push((depth, node));
+        ControlFlow::Continue(WalkAction::Continue)
+    });
+
+    assert_eq!(flow, ControlFlow::Continue(()));
+    assert!(nodes.into_iter().eq(dt.iter_dfs_with_depth()));
+}
+
+#[test]
+fn walk_skips_children() {
+    let dt = DeviceTree::load(DTB).unwrap();
+    let soc = dt.find("/soc").unwrap();
+
+    let mut names = Vec::new();
+    let _ = dt.root.walk(|node, depth| {
+        names.push(node.name());
+        if depth == 1 && node.name() != "cpus" {
+            return ControlFlow::Continue(WalkAction::SkipChildren);
+        }
+        ControlFlow::Continue(WalkAction::Continue)
+    });
+
+    assert!(names.contains(&"soc"));
+    assert!(names.contains(&"cpu@3"));
+    assert!(!soc.children().iter().any(|n| names.contains(&n.name())));
+    assert_eq!(names.len(), dt.root.children().len() + 1 + 4);
+
+    // skipping the children of the starting node walks nothing else
+    let mut walked = 0;
+    let _ = soc.walk(|_, _| {
+        walked += 1;
+        ControlFlow::Continue(WalkAction::SkipChildren)
+    });
+    assert_eq!(walked, 1);
+}
+
+#[test]
+fn walk_stops_on_break() {
+    let dt = DeviceTree::load(DTB).unwrap();
+
+    let mut walked = Vec::new();
+    let flow = dt.root.walk(|node, _| {
+        walked.push(node.name());
+        if node.is_compatible("arm,pl011") {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(WalkAction::Continue)
+    });
+
+    assert_eq!(flow, ControlFlow::Break(()));
+    assert_eq!(walked.last(), Some(&"uart@7e201000"));
+    // nothing after the match was walked
+    let position = dt.iter().position(|n| n.name() == "uart@7e201000").unwrap();
+    assert_eq!(walked.len(), position + 1);
+}
+
+#[test]
+fn walk_mut_rewrites_subtree() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+    let soc_nodes = dt.find("/soc").unwrap().iter().count();
+
+    let mut walked = 0;
+    let soc = dt.find_node_mut("/soc").unwrap();
+    let flow = soc.walk_mut(|node, _| {
+        walked += 1;
+        if node.has_property("status") {
+            node.set_property("status", b"disabled\0".to_vec()).unwrap();
+        }
+        ControlFlow::Continue(WalkAction::Continue)
+    });
+    assert_eq!(flow, ControlFlow::Continue(()));
+    assert_eq!(walked, soc_nodes);
+
+    let dt = DeviceTree::load(&dt.store().unwrap()).unwrap();
+    let soc = dt.find("/soc").unwrap();
+    assert!(soc
+        .iter()
+        .filter(|n| n.has_property("status"))
+        .all(|n| n.property_str("status") == Some("disabled")));
+    // nothing outside the subtree was touched
+    assert_eq!(dt.find_enabled_compatible("arm,cortex-a7").count(), 4);
+}
+
+#[test]
+fn walk_mut_skips_and_stops() {
+    let mut dt = DeviceTree::load(DTB).unwrap();
+
+    // add a child to every top-level node, without walking below them
+    let _ = dt.root.walk_mut(|node, depth| {
+        if depth == 0 {
+            return ControlFlow::Continue(WalkAction::Continue);
+        }
+        node.add_child(Node::new("marker")).unwrap();
+        ControlFlow::Continue(WalkAction::SkipChildren)
+    });
+    assert!(dt
+        .root
+        .children()
+        .iter()
+        .all(|n| n.find_child("marker").is_some()));
+    assert!(dt.find("/soc/uart@7e201000/marker").is_none());
+
+    // children added by the callback are walked
+    let mut markers = 0;
+    let flow = dt.root.walk_mut(|node, _| {
+        if node.name() == "marker" {
+            markers += 1;
+        }
+        if node.name() == "cpus" {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(WalkAction::Continue)
+    });
+    assert_eq!(flow, ControlFlow::Break(()));
+    let cpus = dt.root.children().iter().position(|n| n.name() == "cpus");
+    assert_eq!(markers, cpus.unwrap());
+}